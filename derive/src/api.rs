@@ -4,14 +4,16 @@ use darling::{util::SpannedValue, FromMeta};
 use http::header::HeaderName;
 use indexmap::IndexMap;
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    AttributeArgs, Error, FnArg, ImplItem, ImplItemMethod, ItemImpl, Lit, Meta, NestedMeta, Path,
-    ReturnType,
+    spanned::Spanned, AttributeArgs, Error, FnArg, ImplItem, ImplItemMethod, ItemImpl, Lit, Meta,
+    NestedMeta, Path, ReturnType,
 };
 
 use crate::{
-    common_args::{APIMethod, DefaultValue, MaximumValidator, MinimumValidator, ParamIn},
+    common_args::{
+        APIMethod, DefaultValue, MaximumValidator, MinimumValidator, ParamIn, ParamStyle,
+    },
     error::GeneratorResult,
     utils::{
         convert_oai_path, get_crate_name, get_summary_and_description, optional_literal,
@@ -24,6 +26,8 @@ use crate::{
 struct APIArgs {
     #[darling(default)]
     internal: bool,
+    #[darling(default, multiple, rename = "security")]
+    security: Vec<Path>,
 }
 
 #[derive(FromMeta)]
@@ -34,6 +38,10 @@ struct APIOperation {
     deprecated: bool,
     #[darling(default, multiple, rename = "tag")]
     tags: Vec<Path>,
+    #[darling(default)]
+    skip_error_responses: bool,
+    #[darling(default)]
+    body_limit: Option<usize>,
 }
 
 #[derive(Default)]
@@ -83,6 +91,10 @@ struct APIOperationParam {
     deprecated: bool,
     #[darling(default)]
     default: Option<DefaultValue>,
+    #[darling(default)]
+    style: Option<ParamStyle>,
+    #[darling(default)]
+    explode: Option<bool>,
 
     #[darling(default)]
     multiple_of: Option<SpannedValue<f64>>,
@@ -111,15 +123,17 @@ struct Context {
     operations: IndexMap<String, Vec<TokenStream>>,
     request_types: Vec<TokenStream>,
     response_types: Vec<TokenStream>,
+    error_response_types: Vec<(TokenStream, TokenStream)>,
     tags: Vec<TokenStream>,
     security_schemes: Vec<TokenStream>,
+    default_security: Vec<Path>,
 }
 
 pub(crate) fn generate(
     args: AttributeArgs,
     mut item_impl: ItemImpl,
 ) -> GeneratorResult<TokenStream> {
-    let APIArgs { internal } = match APIArgs::from_list(&args) {
+    let APIArgs { internal, security } = match APIArgs::from_list(&args) {
         Ok(args) => args,
         Err(err) => return Ok(err.write_errors()),
     };
@@ -130,8 +144,10 @@ pub(crate) fn generate(
         operations: Default::default(),
         request_types: Default::default(),
         response_types: Default::default(),
+        error_response_types: Default::default(),
         tags: Default::default(),
         security_schemes: Default::default(),
+        default_security: security,
     };
 
     for item in &mut item_impl.items {
@@ -154,8 +170,10 @@ pub(crate) fn generate(
         operations,
         request_types,
         response_types,
+        error_response_types,
         tags,
         security_schemes,
+        ..
     } = ctx;
 
     let paths = {
@@ -193,6 +211,13 @@ pub(crate) fn generate(
         for ty in response_types {
             register_items.push(quote!(<#ty as #crate_name::Response>::register(registry);));
         }
+        for (ty, has_errors) in error_response_types {
+            register_items.push(quote! {
+                if !<#ty as #crate_name::Response>::BAD_REQUEST_HANDLER && #has_errors {
+                    #crate_name::ParseRequestError::register(registry);
+                }
+            });
+        }
         for tag in tags {
             register_items.push(quote!(#crate_name::Tags::register(&#tag, registry);));
         }
@@ -238,6 +263,8 @@ fn generate_operation(
         method,
         deprecated,
         tags,
+        skip_error_responses,
+        body_limit,
     } = args;
     let http_method = method.to_http_method();
     let fn_ident = &item_method.sig.ident;
@@ -281,7 +308,36 @@ fn generate_operation(
     let mut has_request_payload = false;
     let mut request_meta = quote!(::std::option::Option::None);
     let mut params_meta = Vec::new();
-    let mut security_requirement = quote!(::std::option::Option::None);
+    let mut security_requirements = Vec::new();
+    let mut has_params = false;
+    let mut has_auth = false;
+    let mut scope_checks = Vec::new();
+    // The parameters are parsed into results, and the errors of all of them are
+    // reported together after the last one.
+    let mut param_names = Vec::new();
+    let mut params_end = 0;
+
+    // The names of the query parameters and the security schemes, an exploded
+    // `form` map takes all the other query parameters.
+    let mut query_param_names = Vec::new();
+    let mut security_tys = Vec::new();
+    for arg in item_method.sig.inputs.iter().skip(1) {
+        if let FnArg::Typed(pat) = arg {
+            if let Some(operation_param) = parse_oai_attrs::<APIOperationParam>(&pat.attrs)? {
+                if operation_param.auth.is_some() {
+                    let ty = &pat.ty;
+                    security_tys.push(quote!(#ty));
+                } else if let (Some(name), Some(ParamIn::Query)) =
+                    (operation_param.name, operation_param.param_in)
+                {
+                    query_param_names.push(name);
+                }
+            }
+        }
+    }
+    if security_tys.is_empty() {
+        security_tys.extend(ctx.default_security.iter().map(|ty| quote!(#ty)));
+    }
 
     for i in 1..item_method.sig.inputs.len() {
         let arg = &mut item_method.sig.inputs[i];
@@ -300,11 +356,12 @@ fn generate_operation(
         match operation_param {
             // is poem extractor
             Some(operation_param) if operation_param.extract => {
+                has_params = true;
                 parse_args.push(quote! {
                     let #pname = match <#arg_ty as #crate_name::poem::FromRequest>::from_request(&request, &mut body).await.map_err(|err| #crate_name::ParseRequestError::Extractor(::std::string::ToString::to_string(err))) {
                         ::std::result::Result::Ok(value) => value,
                         ::std::result::Result::Err(err) if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER => {
-                                return ::std::result::Result::Ok(<#res_ty as #crate_name::Response>::from_parse_request_error(err));
+                                return ::std::result::Result::Ok(#crate_name::poem::IntoResponse::into_response(<#res_ty as #crate_name::Response>::from_parse_request_error(err)));
                             },
                         ::std::result::Result::Err(err) => return ::std::result::Result::Err(::std::convert::Into::into(err)),
                     };
@@ -315,24 +372,45 @@ fn generate_operation(
             // is authorization extractor
             Some(operation_param) if operation_param.auth.is_some() => {
                 let auth = operation_param.auth.as_ref().unwrap();
+                let scopes = &auth.scopes;
+                has_auth = true;
+                if !scopes.is_empty() {
+                    scope_checks
+                        .push(quote!(<#arg_ty as #crate_name::SecurityScheme>::CHECK_SCOPES));
+                    parse_args.push(quote_spanned! { arg_ty.span() =>
+                        const _: () = ::std::assert!(
+                            <#arg_ty as #crate_name::SecurityScheme>::CHECK_SCOPES,
+                            "The scopes are required, but the security scheme does not check them, add the `check_scopes` attribute to it.",
+                        );
+                    });
+                }
+                let from_request = if !scopes.is_empty() {
+                    quote! {
+                        <#arg_ty as #crate_name::SecurityScheme>::from_request(&request, &query)
+                            .await
+                            .and_then(|value| <#arg_ty as #crate_name::SecurityScheme>::check_scopes(&value, &[#(#scopes),*]).map(|()| value))
+                    }
+                } else {
+                    quote!(<#arg_ty as #crate_name::SecurityScheme>::from_request(&request, &query).await)
+                };
                 parse_args.push(quote! {
-                    let #pname = match <#arg_ty as #crate_name::SecurityScheme>::from_request(&request, &query.0) {
+                    let #pname = match #from_request {
                         ::std::result::Result::Ok(value) => value,
                         ::std::result::Result::Err(err) if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER => {
-                                return ::std::result::Result::Ok(<#res_ty as #crate_name::Response>::from_parse_request_error(err));
+                                return ::std::result::Result::Ok(#crate_name::poem::IntoResponse::into_response(<#res_ty as #crate_name::Response>::from_parse_request_error(err)));
                             },
                         ::std::result::Result::Err(err) => return ::std::result::Result::Err(::std::convert::Into::into(err)),
                     };
                 });
                 use_args.push(pname);
 
-                let scopes = &auth.scopes;
-                security_requirement = quote!(::std::option::Option::Some((<#arg_ty as #crate_name::SecurityScheme>::NAME, ::std::vec![#(#scopes),*])));
+                security_requirements.push(quote!((<#arg_ty as #crate_name::SecurityScheme>::register, <#arg_ty as #crate_name::SecurityScheme>::security_schemes(), ::std::vec![#(#scopes),*], <#arg_ty as #crate_name::SecurityScheme>::IS_OPTIONAL)));
                 ctx.security_schemes.push(quote!(#arg_ty));
             }
 
             // is parameter
             Some(operation_param) => {
+                has_params = true;
                 let param_oai_typename = match &operation_param.name {
                     Some(name) => name.clone(),
                     None => {
@@ -377,13 +455,98 @@ fn generate_operation(
                     .into());
                 }
 
+                if param_in != ParamIn::Query
+                    && (operation_param.style.is_some() || operation_param.explode.is_some())
+                {
+                    return Err(Error::new_spanned(
+                        arg,
+                        "The `style` and `explode` attributes are only supported for query parameters.",
+                    )
+                    .into());
+                } else if operation_param.style == Some(ParamStyle::DeepObject)
+                    && operation_param.explode == Some(false)
+                {
+                    return Err(Error::new_spanned(
+                        arg,
+                        "The `deepObject` style requires `explode` to be true.",
+                    )
+                    .into());
+                }
+
                 let meta_in = {
                     let ty = param_in.to_meta();
                     quote!(#crate_name::registry::MetaParamIn::#ty)
                 };
-                let validators_checker = operation_param
-                    .validators()
-                    .create_param_checker(crate_name, &param_oai_typename)?;
+                let meta_style = match operation_param.style {
+                    Some(style) => {
+                        let style = style.to_meta();
+                        quote!(::std::option::Option::Some(#crate_name::registry::MetaParamStyle::#style))
+                    }
+                    None => quote!(::std::option::Option::None),
+                };
+                let meta_explode = match operation_param.explode {
+                    Some(explode) => quote!(::std::option::Option::Some(#explode)),
+                    None => quote!(::std::option::Option::None),
+                };
+                let (get_value, is_missing, parse_value, actual_value) = if param_in
+                    == ParamIn::Query
+                {
+                    let explode = operation_param.explode.unwrap_or(true);
+                    let other_params = query_param_names
+                        .iter()
+                        .filter(|name| **name != param_oai_typename);
+                    (
+                        quote! {
+                            #[allow(unused_mut)]
+                            let mut other_params: ::std::vec::Vec<&str> = ::std::vec![#(#other_params),*];
+                            #(::std::iter::Extend::extend(&mut other_params, #crate_name::param::security_query_params::<#security_tys>());)*
+                            let values = #crate_name::param::get_query_values::<#arg_ty>(
+                                #param_oai_typename,
+                                #meta_style,
+                                #explode,
+                                &other_params,
+                                &query_pairs,
+                            );
+                        },
+                        quote!(values.is_empty()),
+                        quote!(#crate_name::types::ParseFromParameter::parse_from_parameters(&values)),
+                        quote! {
+                            match values.as_slice() {
+                                [value] => ::std::option::Option::Some(#crate_name::serde_json::Value::String(::std::string::ToString::to_string(value))),
+                                values => ::std::option::Option::Some(#crate_name::serde_json::Value::Array(values.iter().map(|value| #crate_name::serde_json::Value::String(::std::string::ToString::to_string(value))).collect())),
+                            }
+                        },
+                    )
+                } else {
+                    (
+                        quote! {
+                            let param_value = #crate_name::param::get(#param_oai_typename, #meta_in, &request, &query);
+                            let param_value = param_value.as_deref();
+                        },
+                        quote!(param_value.is_none()),
+                        quote!(#crate_name::types::ParseFromParameter::parse_from_parameter(param_value)),
+                        quote!(param_value.map(|value| #crate_name::serde_json::Value::String(::std::string::ToString::to_string(value)))),
+                    )
+                };
+                let validators_checker = operation_param.validators().create_param_checker(
+                    crate_name,
+                    &param_oai_typename,
+                    actual_value,
+                )?;
+                let parse_value = quote! {
+                    (|| -> ::std::result::Result<#arg_ty, #crate_name::ParseRequestError> {
+                        let value = #parse_value.map_err(|err| {
+                            let violations = ::std::borrow::ToOwned::to_owned(err.violations());
+                            #crate_name::ParseRequestError::ParseParam {
+                                name: #param_oai_typename,
+                                reason: err.into_message(),
+                                violations,
+                            }
+                        })?;
+                        #validators_checker
+                        ::std::result::Result::Ok(value)
+                    })()
+                };
                 let validators_update_meta = operation_param
                     .validators()
                     .create_update_meta(crate_name)?;
@@ -399,27 +562,11 @@ fn generate_operation(
 
                         parse_args.push(quote! {
                             let #pname = {
-                                let value = #crate_name::param::get(#param_oai_typename, #meta_in, &request, &query.0);
-                                let value = value.as_deref();
-                                match value {
-                                    Some(value) => {
-                                        match #crate_name::types::ParseFromParameter::parse_from_parameter(Some(value))
-                                                .map_err(|err| #crate_name::ParseRequestError::ParseParam {
-                                                    name: #param_oai_typename,
-                                                    reason: err.into_message(),
-                                                })
-                                        {
-                                            ::std::result::Result::Ok(value) => {
-                                                #validators_checker
-                                                value
-                                            },
-                                            ::std::result::Result::Err(err) if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER => {
-                                                return ::std::result::Result::Ok(<#res_ty as #crate_name::Response>::from_parse_request_error(err));
-                                            },
-                                            ::std::result::Result::Err(err) => return ::std::result::Result::Err(#crate_name::poem::Error::from(err)),
-                                        }
-                                    }
-                                    None => #default_value,
+                                #get_value
+                                if #is_missing {
+                                    ::std::result::Result::Ok(#default_value)
+                                } else {
+                                    #parse_value
                                 }
                             };
                         });
@@ -427,22 +574,8 @@ fn generate_operation(
                     None => {
                         parse_args.push(quote! {
                             let #pname = {
-                                let value = #crate_name::param::get(#param_oai_typename, #meta_in, &request, &query.0);
-                                match #crate_name::types::ParseFromParameter::parse_from_parameter(value.as_deref())
-                                        .map_err(|err| #crate_name::ParseRequestError::ParseParam {
-                                            name: #param_oai_typename,
-                                            reason: err.into_message(),
-                                        })
-                                {
-                                    ::std::result::Result::Ok(value) => {
-                                        #validators_checker
-                                        value
-                                    },
-                                    ::std::result::Result::Err(err) if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER => {
-                                        return ::std::result::Result::Ok(<#res_ty as #crate_name::Response>::from_parse_request_error(err));
-                                    },
-                                    ::std::result::Result::Err(err) => return ::std::result::Result::Err(::std::convert::Into::into(err)),
-                                }
+                                #get_value
+                                #parse_value
                             };
                         });
                     }
@@ -458,6 +591,8 @@ fn generate_operation(
                     None => quote!(::std::option::Option::None),
                 };
 
+                param_names.push(pname.clone());
+                params_end = parse_args.len();
                 use_args.push(pname);
 
                 let desc = optional_literal(&operation_param.desc);
@@ -480,6 +615,8 @@ fn generate_operation(
                         description: #desc,
                         required: <#arg_ty as #crate_name::types::Type>::IS_REQUIRED,
                         deprecated: #deprecated,
+                        style: #meta_style,
+                        explode: #meta_explode,
                     }
                 });
             }
@@ -492,11 +629,19 @@ fn generate_operation(
                     );
                 }
 
+                let from_request = match body_limit {
+                    Some(_) => {
+                        quote!(body_limit.from_request::<#arg_ty>(&request, &mut body))
+                    }
+                    None => {
+                        quote!(<#arg_ty as #crate_name::Request>::from_request(&request, &mut body))
+                    }
+                };
                 parse_args.push(quote! {
-                    let #pname = match <#arg_ty as #crate_name::Request>::from_request(&request, &mut body).await {
+                    let #pname = match #from_request.await {
                         ::std::result::Result::Ok(value) => value,
                         ::std::result::Result::Err(err) if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER => {
-                                return ::std::result::Result::Ok(<#res_ty as #crate_name::Response>::from_parse_request_error(err));
+                                return ::std::result::Result::Ok(#crate_name::poem::IntoResponse::into_response(<#res_ty as #crate_name::Response>::from_parse_request_error(err)));
                             },
                         ::std::result::Result::Err(err) => return ::std::result::Result::Err(::std::convert::Into::into(err)),
                     };
@@ -511,8 +656,68 @@ fn generate_operation(
         }
     }
 
+    if !param_names.is_empty() {
+        parse_args.insert(params_end, quote! {
+            let (#(#param_names,)*) = match (#(#param_names,)*) {
+                (#(::std::result::Result::Ok(#param_names),)*) => (#(#param_names,)*),
+                (#(#param_names,)*) => {
+                    let mut errors = ::std::vec::Vec::new();
+                    #(::std::iter::Extend::extend(&mut errors, ::std::result::Result::err(#param_names));)*
+                    let err = #crate_name::ParseRequestError::merge_params(errors);
+                    if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER {
+                        return ::std::result::Result::Ok(#crate_name::poem::IntoResponse::into_response(<#res_ty as #crate_name::Response>::from_parse_request_error(err)));
+                    }
+                    return ::std::result::Result::Err(::std::convert::Into::into(err));
+                }
+            };
+        });
+    }
+
+    if !has_auth && !ctx.default_security.is_empty() {
+        has_auth = true;
+        for (i, ty) in ctx.default_security.iter().enumerate() {
+            parse_args.insert(i, quote! {
+                match <#ty as #crate_name::SecurityScheme>::from_request(&request, &query).await {
+                    ::std::result::Result::Ok(_) => {}
+                    ::std::result::Result::Err(err) if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER => {
+                            return ::std::result::Result::Ok(#crate_name::poem::IntoResponse::into_response(<#res_ty as #crate_name::Response>::from_parse_request_error(err)));
+                        },
+                    ::std::result::Result::Err(err) => return ::std::result::Result::Err(::std::convert::Into::into(err)),
+                }
+            });
+            security_requirements.push(quote!((<#ty as #crate_name::SecurityScheme>::register, <#ty as #crate_name::SecurityScheme>::security_schemes(), ::std::vec::Vec::new(), <#ty as #crate_name::SecurityScheme>::IS_OPTIONAL)));
+            ctx.security_schemes.push(quote!(#ty));
+        }
+    }
+
+    if body_limit.is_some() && !has_request_payload {
+        return Err(Error::new_spanned(
+            fn_ident,
+            "The `body_limit` attribute requires a request body.",
+        )
+        .into());
+    }
+
     ctx.response_types.push(quote!(#res_ty));
 
+    // A streaming payload is read by the operation, so the limit is checked
+    // again after it returns.
+    let (create_body_limit, check_body_limit) = match body_limit {
+        Some(limit) => (
+            quote!(let body_limit = #crate_name::payload::BodyLimit::new(#limit);),
+            quote! {
+                match body_limit.check() {
+                    ::std::result::Result::Ok(()) => {}
+                    ::std::result::Result::Err(err) if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER => {
+                            return ::std::result::Result::Ok(#crate_name::poem::IntoResponse::into_response(<#res_ty as #crate_name::Response>::from_parse_request_error(err)));
+                        },
+                    ::std::result::Result::Err(err) => return ::std::result::Result::Err(::std::convert::Into::into(err)),
+                }
+            },
+        ),
+        None => (quote!(), quote!()),
+    };
+
     ctx.add_routes.entry(new_path).or_default().push(quote! {
         method(#crate_name::poem::http::Method::#http_method, {
             let api_obj = ::std::clone::Clone::clone(&api_obj);
@@ -520,9 +725,20 @@ fn generate_operation(
                 let api_obj = ::std::clone::Clone::clone(&api_obj);
                 async move {
                     let (request, mut body) = request.split();
-                    let query = <#crate_name::poem::web::Query::<::std::collections::HashMap<::std::string::String, ::std::string::String>> as #crate_name::poem::FromRequest>::from_request(&request, &mut body).await.unwrap_or_default();
+                    let query_pairs = <#crate_name::poem::web::Query::<::std::vec::Vec<(::std::string::String, ::std::string::String)>> as #crate_name::poem::FromRequest>::from_request(&request, &mut body).await.map(|query| query.0).unwrap_or_default();
+                    let query: ::std::collections::HashMap<::std::string::String, ::std::string::String> = ::std::iter::FromIterator::from_iter(::std::iter::IntoIterator::into_iter(::std::clone::Clone::clone(&query_pairs)));
+                    match <#res_ty as #crate_name::Response>::check_accept(&request) {
+                        ::std::result::Result::Ok(()) => {}
+                        ::std::result::Result::Err(err) if <#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER => {
+                                return ::std::result::Result::Ok(#crate_name::poem::IntoResponse::into_response(<#res_ty as #crate_name::Response>::from_parse_request_error(err)));
+                            },
+                        ::std::result::Result::Err(err) => return ::std::result::Result::Err(::std::convert::Into::into(err)),
+                    }
+                    #create_body_limit
                     #(#parse_args)*
-                    ::std::result::Result::Ok::<_, #crate_name::poem::Error>(api_obj.#fn_ident(#(#use_args),*).await)
+                    let resp = api_obj.#fn_ident(#(#use_args),*).await;
+                    #check_body_limit
+                    <#res_ty as #crate_name::Response>::into_response_with_request(resp, ::std::option::Option::Some(&request))
                 }
             })
         })
@@ -534,6 +750,46 @@ fn generate_operation(
         tag_names.push(quote!(#crate_name::Tags::name(&#tag)));
     }
 
+    // The statuses of the errors that may be returned, and whether they are
+    // returned for the response type.
+    let mut error_statuses = Vec::new();
+    if !skip_error_responses {
+        if has_params || has_request_payload {
+            error_statuses.push(quote!((400u16, true)));
+        }
+        if has_auth {
+            error_statuses.push(quote!((401u16, false #(|| !<#security_tys as #crate_name::SecurityScheme>::IS_OPTIONAL)*)));
+        }
+        if !scope_checks.is_empty() {
+            error_statuses.push(quote!((403u16, false #(|| #scope_checks)*)));
+        }
+        error_statuses.push(quote!((406u16, <#res_ty as #crate_name::Response>::NEGOTIATED)));
+        if body_limit.is_some() {
+            error_statuses.push(quote!((413u16, true)));
+        }
+        if has_request_payload {
+            error_statuses.push(quote!((415u16, true)));
+        }
+    }
+    let responses_meta = if error_statuses.is_empty() {
+        quote!(<#res_ty as #crate_name::Response>::meta())
+    } else {
+        let has_errors = quote!(::std::iter::Iterator::any(&mut [#(#error_statuses),*].iter(), |(_, enabled)| *enabled));
+        ctx.error_response_types
+            .push((quote!(#res_ty), has_errors.clone()));
+        quote! {{
+            let mut responses = <#res_ty as #crate_name::Response>::meta();
+            if !<#res_ty as #crate_name::Response>::BAD_REQUEST_HANDLER {
+                for (status, enabled) in [#(#error_statuses),*] {
+                    if enabled && !::std::iter::Iterator::any(&mut responses.responses.iter(), |resp| resp.status == ::std::option::Option::Some(status)) {
+                        responses.responses.push(#crate_name::ParseRequestError::meta_response(status));
+                    }
+                }
+            }
+            responses
+        }}
+    };
+
     ctx.operations.entry(oai_path).or_default().push(quote! {
         #crate_name::registry::MetaOperation {
             tags: ::std::vec![#(#tag_names),*],
@@ -542,9 +798,9 @@ fn generate_operation(
             description: #description,
             params: ::std::vec![#(#params_meta),*],
             request: #request_meta,
-            responses: <#res_ty as #crate_name::Response>::meta(),
+            responses: #responses_meta,
             deprecated: #deprecated,
-            security: ::std::vec![::std::iter::FromIterator::from_iter(::std::iter::IntoIterator::into_iter(#security_requirement))],
+            security: #crate_name::registry::MetaOperation::security_requirements(::std::vec![#(#security_requirements),*]),
         }
     });
 
@@ -143,6 +143,29 @@ impl ParamIn {
     }
 }
 
+#[derive(Debug, Copy, Clone, FromMeta, Eq, PartialEq)]
+pub(crate) enum ParamStyle {
+    #[darling(rename = "form")]
+    Form,
+    #[darling(rename = "spaceDelimited")]
+    SpaceDelimited,
+    #[darling(rename = "pipeDelimited")]
+    PipeDelimited,
+    #[darling(rename = "deepObject")]
+    DeepObject,
+}
+
+impl ParamStyle {
+    pub(crate) fn to_meta(self) -> TokenStream {
+        match self {
+            ParamStyle::Form => quote!(Form),
+            ParamStyle::SpaceDelimited => quote!(SpaceDelimited),
+            ParamStyle::PipeDelimited => quote!(PipeDelimited),
+            ParamStyle::DeepObject => quote!(DeepObject),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum DefaultValue {
     Default,
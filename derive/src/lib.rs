@@ -13,6 +13,7 @@ mod request;
 mod response;
 mod security_scheme;
 mod tags;
+mod union;
 mod utils;
 
 use proc_macro::TokenStream;
@@ -91,3 +92,12 @@ pub fn derive_security_scheme(input: TokenStream) -> TokenStream {
         Err(err) => err.write_errors().into(),
     }
 }
+
+#[proc_macro_derive(Union, attributes(oai))]
+pub fn derive_union(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as DeriveInput);
+    match union::generate(args) {
+        Ok(stream) => stream.into(),
+        Err(err) => err.write_errors().into(),
+    }
+}
@@ -119,14 +119,16 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             if field.name() == ::std::option::Option::Some(#field_name) {
                 #field_ident = match #field_ident {
                     ::std::option::Option::Some(value) => {
-                        ::std::option::Option::Some(<#field_ty as #crate_name::types::ParseFromMultipartField>::parse_from_repeated_field(value, field).await.map_err(|err|
-                            #crate_name::ParseRequestError::ParseRequestBody { reason: ::std::format!("failed to parse field `{}`: {}", #field_name, err.into_message()) }
-                        )?)
+                        ::std::option::Option::Some(<#field_ty as #crate_name::types::ParseFromMultipartField>::parse_from_repeated_field(value, field).await.map_err(|err| {
+                            let violations = ::std::borrow::ToOwned::to_owned(err.violations());
+                            #crate_name::ParseRequestError::ParseRequestBody { reason: ::std::format!("failed to parse field `{}`: {}", #field_name, err.into_message()), violations }
+                        })?)
                     }
                     ::std::option::Option::None => {
-                        ::std::option::Option::Some(<#field_ty as #crate_name::types::ParseFromMultipartField>::parse_from_multipart(::std::option::Option::Some(field)).await.map_err(|err|
-                            #crate_name::ParseRequestError::ParseRequestBody { reason: ::std::format!("failed to parse field `{}`: {}", #field_name, err.into_message()) }
-                        )?)
+                        ::std::option::Option::Some(<#field_ty as #crate_name::types::ParseFromMultipartField>::parse_from_multipart(::std::option::Option::Some(field)).await.map_err(|err| {
+                            let violations = ::std::borrow::ToOwned::to_owned(err.violations());
+                            #crate_name::ParseRequestError::ParseRequestBody { reason: ::std::format!("failed to parse field `{}`: {}", #field_name, err.into_message()), violations }
+                        })?)
                     }
                 };
                 continue;
@@ -161,7 +163,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         },
                         ::std::option::Option::None => {
                             <#field_ty as #crate_name::types::ParseFromMultipartField>::parse_from_multipart(::std::option::Option::None).await.map_err(|_|
-                                #crate_name::ParseRequestError::ParseRequestBody { reason: ::std::format!("field `{}` is required", #field_name) }
+                                #crate_name::ParseRequestError::ParseRequestBody { reason: ::std::format!("field `{}` is required", #field_name), violations: ::std::vec::Vec::new() }
                             )?
                         }
                     };
@@ -237,10 +239,11 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 if body.is_some() {
                     let mut multipart = <#crate_name::poem::web::Multipart as #crate_name::poem::FromRequest>::from_request(request, body).await.map_err(|err| #crate_name::ParseRequestError::ParseRequestBody {
                         reason: ::std::string::ToString::to_string(&err),
+                        violations: ::std::vec::Vec::new(),
                     })?;
                     #(#skip_fields)*
                     #(let mut #fields = ::std::option::Option::None;)*
-                    while let Some(field) = multipart.next_field().await.map_err(|err| #crate_name::ParseRequestError::ParseRequestBody { reason: ::std::string::ToString::to_string(&err) })? {
+                    while let Some(field) = multipart.next_field().await.map_err(|err| #crate_name::ParseRequestError::ParseRequestBody { reason: ::std::string::ToString::to_string(&err), violations: ::std::vec::Vec::new() })? {
                         #(#deserialize_fields)*
                     }
                     #(#deserialize_none)*
@@ -248,6 +251,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 } else {
                     Err(#crate_name::ParseRequestError::ParseRequestBody {
                         reason: ::std::convert::Into::into("expect request body"),
+                        violations: ::std::vec::Vec::new(),
                     })
                 }
             }
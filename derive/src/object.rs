@@ -1,11 +1,11 @@
 use darling::{
     ast::Data,
     util::{Ignored, SpannedValue},
-    FromDeriveInput, FromField,
+    FromDeriveInput, FromField, FromMeta,
 };
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
-use syn::{ext::IdentExt, Attribute, DeriveInput, Error, Generics, Type};
+use quote::{quote, quote_spanned};
+use syn::{ext::IdentExt, spanned::Spanned, Attribute, DeriveInput, Error, Generics, Type};
 
 use crate::{
     common_args::{
@@ -17,6 +17,20 @@ use crate::{
     validators::HasValidators,
 };
 
+#[derive(FromMeta)]
+struct XmlArgs {
+    #[darling(default)]
+    name: Option<String>,
+    #[darling(default)]
+    namespace: Option<String>,
+    #[darling(default)]
+    prefix: Option<String>,
+    #[darling(default)]
+    attribute: bool,
+    #[darling(default)]
+    wrapped: bool,
+}
+
 #[derive(FromField)]
 #[darling(attributes(oai), forward_attrs(doc))]
 struct ObjectField {
@@ -26,11 +40,21 @@ struct ObjectField {
 
     #[darling(default)]
     skip: bool,
+    #[darling(default)]
+    flatten: bool,
+    #[darling(default)]
+    extends: bool,
+    #[darling(default)]
+    read_only: bool,
+    #[darling(default)]
+    write_only: bool,
 
     #[darling(default)]
     name: Option<String>,
     #[darling(default)]
     default: Option<DefaultValue>,
+    #[darling(default)]
+    xml: Option<XmlArgs>,
 
     #[darling(default)]
     multiple_of: Option<SpannedValue<f64>>,
@@ -70,6 +94,10 @@ struct ObjectArgs {
     concretes: Vec<ConcreteType>,
     #[darling(default)]
     deprecated: bool,
+    #[darling(default)]
+    deny_unknown_fields: bool,
+    #[darling(default)]
+    extends: Option<Type>,
 
     #[darling(default)]
     name: Option<String>,
@@ -99,24 +127,132 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let mut serialize_fields = Vec::new();
     let mut fields = Vec::new();
     let mut meta_fields = Vec::new();
-    let mut required_fields = Vec::new();
+    let mut extends = Vec::new();
+    let mut field_names = Vec::new();
 
     for field in &s.fields {
         let field_ident = field.ident.as_ref().unwrap();
         let field_ty = &field.ty;
+        let is_base = field.extends;
 
         if field.skip {
             deserialize_fields.push(quote! {
-                let #field_ident: #field_ty = ::std::default::Default::default();
+                let #field_ident: ::std::option::Option<#field_ty> = ::std::option::Option::Some(::std::default::Default::default());
+            });
+            fields.push(field_ident);
+            continue;
+        }
+
+        if field.flatten || is_base {
+            if args.deny_unknown_fields {
+                return Err(Error::new_spanned(
+                    field_ident,
+                    "The `deny_unknown_fields` attribute cannot be used with flattened or extended fields.",
+                )
+                .into());
+            }
+
+            if field.flatten && is_base {
+                return Err(Error::new_spanned(
+                    field_ident,
+                    "The field of the extended object cannot be flattened.",
+                )
+                .into());
+            }
+
+            let base_ty = match (is_base, &args.extends) {
+                (true, Some(base_ty)) => Some(base_ty),
+                (true, None) => {
+                    return Err(Error::new_spanned(
+                        field_ident,
+                        r#"The `extends` field requires the extended type of the object. #[oai(extends = "...")]"#,
+                    )
+                    .into())
+                }
+                (false, _) => None,
+            };
+
+            if is_base && !extends.is_empty() {
+                return Err(Error::new_spanned(
+                    field_ident,
+                    "Only one field can hold the extended object.",
+                )
+                .into());
+            }
+
+            deserialize_fields.push(quote! {
+                #[allow(non_snake_case)]
+                let #field_ident: ::std::option::Option<#field_ty> = match #crate_name::types::ParseFromJSON::parse_from_json(#crate_name::serde_json::Value::Object(::std::clone::Clone::clone(&obj))) {
+                    ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                    ::std::result::Result::Err(err) => {
+                        errors.push(#crate_name::types::ParseError::propagate(err));
+                        ::std::option::Option::None
+                    }
+                };
+            });
+            serialize_fields.push(quote! {
+                if let #crate_name::serde_json::Value::Object(value) = #crate_name::types::ToJSON::to_json(&self.#field_ident) {
+                    object.extend(value);
+                }
             });
             fields.push(field_ident);
+
+            // The inner object sees the fields of the outer object, so it
+            // cannot deny the unknown fields.
+            let assert_object_type = quote! {
+                struct AssertObjectType<T>(::std::marker::PhantomData<T>);
+                impl<T: #crate_name::types::IsObjectType> AssertObjectType<T> {
+                    const ALLOW_UNKNOWN_FIELDS: () = ::std::assert!(
+                        !T::DENY_UNKNOWN_FIELDS,
+                        "A flattened or extended object cannot use the `deny_unknown_fields` attribute.",
+                    );
+                }
+                #[allow(clippy::let_unit_value)]
+                let _ = AssertObjectType::<#field_ty>::ALLOW_UNKNOWN_FIELDS;
+            };
+
+            if field.flatten {
+                meta_fields.push(quote! {{
+                    #assert_object_type
+                    <#field_ty as #crate_name::types::Type>::register(registry);
+
+                    registry.object_properties(
+                        &<#field_ty as #crate_name::types::Type>::schema_ref(),
+                        &mut properties,
+                        &mut required,
+                    );
+                }});
+            } else {
+                // Fails to compile if the field does not hold the extended type.
+                let base_ty = base_ty.unwrap();
+                let check_base_ty = quote_spanned! { base_ty.span() =>
+                    let _: fn(#field_ty) -> #base_ty = |base| base;
+                };
+                extends.push(quote! {{
+                    #assert_object_type
+                    #check_base_ty
+                    <#field_ty as #crate_name::types::Type>::register(registry);
+                    <#field_ty as #crate_name::types::Type>::schema_ref()
+                }});
+            }
             continue;
         }
 
+        if field.read_only && field.write_only {
+            return Err(Error::new_spanned(
+                field_ident,
+                "The `read_only` and `write_only` attributes cannot be used together.",
+            )
+            .into());
+        }
+
         let field_name = field.name.clone().unwrap_or_else(|| {
             args.rename_fields
                 .rename(field_ident.unraw().to_string(), RenameTarget::Field)
         });
+        if !field.read_only {
+            field_names.push(field_name.clone());
+        }
         let (field_title, field_description) = get_summary_and_description(&field.attrs)?;
         let field_title = optional_literal(&field_title);
         let field_description = optional_literal(&field_description);
@@ -127,46 +263,81 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
 
         fields.push(field_ident);
 
-        match &field.default {
-            Some(default_value) => {
-                let default_value = match default_value {
-                    DefaultValue::Default => {
-                        quote!(<#field_ty as ::std::default::Default>::default())
-                    }
-                    DefaultValue::Function(func_name) => quote!(#func_name()),
-                };
+        let default_value = field
+            .default
+            .as_ref()
+            .map(|default_value| match default_value {
+                DefaultValue::Default => {
+                    quote!(<#field_ty as ::std::default::Default>::default())
+                }
+                DefaultValue::Function(func_name) => quote!(#func_name()),
+            });
 
-                deserialize_fields.push(quote! {
-                    #[allow(non_snake_case)]
-                    let #field_ident: #field_ty = {
-                        match obj.get(#field_name).cloned().unwrap_or_default() {
-                            #crate_name::serde_json::Value::Null => #default_value,
-                            value => {
-                                let value = #crate_name::types::ParseFromJSON::parse_from_json(value).map_err(#crate_name::types::ParseError::propagate)?;
-                                #validators_checker
-                                value
+        if field.read_only {
+            let default_value = match default_value {
+                Some(default_value) => default_value,
+                None => {
+                    return Err(Error::new_spanned(
+                        field_ident,
+                        "A `read_only` field requires a `default` value, which is used when parsing a request.",
+                    )
+                    .into())
+                }
+            };
+            deserialize_fields.push(quote! {
+                #[allow(non_snake_case)]
+                let #field_ident: ::std::option::Option<#field_ty> = ::std::option::Option::Some(#default_value);
+            });
+        } else {
+            match &default_value {
+                Some(default_value) => {
+                    deserialize_fields.push(quote! {
+                        #[allow(non_snake_case)]
+                        let #field_ident: ::std::option::Option<#field_ty> = match (|| -> ::std::result::Result<#field_ty, #crate_name::types::ParseError<Self>> {
+                            match obj.get(#field_name).cloned().unwrap_or_default() {
+                                #crate_name::serde_json::Value::Null => ::std::result::Result::Ok(#default_value),
+                                value => {
+                                    let value = #crate_name::types::ParseFromJSON::parse_from_json(value)
+                                        .map_err(|err| #crate_name::types::ParseError::propagate_field(err, #field_name))?;
+                                    #validators_checker
+                                    ::std::result::Result::Ok(value)
+                                }
                             }
-                        }
-                    };
-                });
-            }
-            _ => {
-                deserialize_fields.push(quote! {
-                    #[allow(non_snake_case)]
-                    let #field_ident: #field_ty = {
-                        let value = #crate_name::types::ParseFromJSON::parse_from_json(obj.get(#field_name).cloned().unwrap_or_default())
-                            .map_err(#crate_name::types::ParseError::propagate)?;
-                        #validators_checker
-                        value
-                    };
-                });
+                        })() {
+                            ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                            ::std::result::Result::Err(err) => {
+                                errors.push(err);
+                                ::std::option::Option::None
+                            }
+                        };
+                    });
+                }
+                _ => {
+                    deserialize_fields.push(quote! {
+                        #[allow(non_snake_case)]
+                        let #field_ident: ::std::option::Option<#field_ty> = match (|| -> ::std::result::Result<#field_ty, #crate_name::types::ParseError<Self>> {
+                            let value = #crate_name::types::ParseFromJSON::parse_from_json(obj.get(#field_name).cloned().unwrap_or_default())
+                                .map_err(|err| #crate_name::types::ParseError::propagate_field(err, #field_name))?;
+                            #validators_checker
+                            ::std::result::Result::Ok(value)
+                        })() {
+                            ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                            ::std::result::Result::Err(err) => {
+                                errors.push(err);
+                                ::std::option::Option::None
+                            }
+                        };
+                    });
+                }
             }
-        };
+        }
 
-        serialize_fields.push(quote! {
-            let value = #crate_name::types::ToJSON::to_json(&self.#field_ident);
-            object.insert(::std::string::ToString::to_string(#field_name), value);
-        });
+        if !field.write_only {
+            serialize_fields.push(quote! {
+                let value = #crate_name::types::ToJSON::to_json(&self.#field_ident);
+                object.insert(::std::string::ToString::to_string(#field_name), value);
+            });
+        }
 
         let field_meta_default = match &field.default {
             Some(DefaultValue::Default) => {
@@ -178,34 +349,79 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             None => quote!(::std::option::Option::None),
         };
 
-        meta_fields.push(quote! {{
-            <#field_ty>::register(registry);
+        let read_only = field.read_only;
+        let write_only = field.write_only;
+        let update_xml = field.xml.as_ref().map(|xml| {
+            let name = optional_literal(&xml.name);
+            let namespace = optional_literal(&xml.namespace);
+            let prefix = optional_literal(&xml.prefix);
+            let attribute = xml.attribute;
+            let wrapped = xml.wrapped;
+            quote! {
+                schema.xml = ::std::option::Option::Some(#crate_name::registry::MetaXml {
+                    name: #name,
+                    namespace: #namespace,
+                    prefix: #prefix,
+                    attribute: #attribute,
+                    wrapped: #wrapped,
+                });
+            }
+        });
+        let update_access_mode = if read_only || write_only || update_xml.is_some() {
+            quote! {
+                if let #crate_name::registry::MetaSchemaRef::Reference(_) = &schema_ref {
+                    schema_ref = #crate_name::registry::MetaSchemaRef::Inline(#crate_name::registry::MetaSchema {
+                        all_of: ::std::vec![schema_ref],
+                        ..#crate_name::registry::MetaSchema::ANY
+                    });
+                }
+                if let #crate_name::registry::MetaSchemaRef::Inline(schema) = &mut schema_ref {
+                    schema.read_only = #read_only;
+                    schema.write_only = #write_only;
+                    #update_xml
+                }
+            }
+        } else {
+            quote!()
+        };
 
-            let mut schema_ref = <#field_ty as #crate_name::types::Type>::schema_ref();
+        meta_fields.push(quote! {
+            properties.push({
+                <#field_ty>::register(registry);
 
-            if let #crate_name::registry::MetaSchemaRef::Inline(schema) = &mut schema_ref {
-                schema.default = #field_meta_default;
+                let mut schema_ref = <#field_ty as #crate_name::types::Type>::schema_ref();
 
-                if let ::std::option::Option::Some(title) = #field_title {
-                    schema.title = ::std::option::Option::Some(title);
-                }
+                if let #crate_name::registry::MetaSchemaRef::Inline(schema) = &mut schema_ref {
+                    schema.default = #field_meta_default;
+
+                    if let ::std::option::Option::Some(title) = #field_title {
+                        schema.title = ::std::option::Option::Some(title);
+                    }
 
-                if let ::std::option::Option::Some(field_description) = #field_description {
-                    schema.description = ::std::option::Option::Some(field_description);
+                    if let ::std::option::Option::Some(field_description) = #field_description {
+                        schema.description = ::std::option::Option::Some(field_description);
+                    }
+                    #validators_update_meta
                 }
-                #validators_update_meta
-            }
+                #update_access_mode
 
-            (#field_name, schema_ref)
-        }});
+                (#field_name, schema_ref)
+            });
 
-        required_fields.push(quote! {
             if <#field_ty>::IS_REQUIRED {
-                fields.push(#field_name);
+                required.push(#field_name);
             }
         });
     }
 
+    if let (Some(base), true) = (&args.extends, extends.is_empty()) {
+        return Err(Error::new_spanned(
+            base,
+            "The object must have a field marked with `#[oai(extends)]` to hold the properties of the extended type.",
+        )
+        .into());
+    }
+
     let meta_object_default = match &args.default {
         Some(DefaultValue::Default) => {
             quote!(::std::option::Option::Some(
@@ -234,24 +450,77 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         }
     };
 
+    // The `read_only` fields are not accepted in the input either.
+    let check_unknown_fields = if args.deny_unknown_fields {
+        quote! {
+            for name in obj.keys() {
+                if ![#(#field_names),*].contains(&name.as_str()) {
+                    errors.push(
+                        #crate_name::types::ParseError::<Self>::custom(::std::format!("unknown field `{}`.", name))
+                            .propagate_field(name),
+                    );
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let additional_properties = if args.deny_unknown_fields {
+        quote!(::std::option::Option::Some(
+            #crate_name::registry::MetaAdditionalProperties::Bool(false)
+        ))
+    } else {
+        quote!(::std::option::Option::None)
+    };
+
     let title = optional_literal(&title);
     let description = optional_literal(&description);
     let deprecated = args.deprecated;
-    let meta = quote! {
-        #crate_name::registry::MetaSchema {
-            title: #title,
-            description: #description,
-            required: {
-                #[allow(unused_mut)]
-                let mut fields = ::std::vec::Vec::new();
-                #(#required_fields)*
-                fields
-            },
-            properties: ::std::vec![#(#meta_fields),*],
-            deprecated: #deprecated,
-            default: #meta_object_default,
-            ..#crate_name::registry::MetaSchema::new("object")
-        }
+    let deny_unknown_fields = args.deny_unknown_fields;
+    let meta = if extends.is_empty() {
+        quote! {{
+            #[allow(unused_mut)]
+            let mut properties = ::std::vec::Vec::new();
+            #[allow(unused_mut)]
+            let mut required = ::std::vec::Vec::new();
+            #(#meta_fields)*
+
+            #crate_name::registry::MetaSchema {
+                title: #title,
+                description: #description,
+                required,
+                properties,
+                additional_properties: #additional_properties,
+                deprecated: #deprecated,
+                default: #meta_object_default,
+                ..#crate_name::registry::MetaSchema::new("object")
+            }
+        }}
+    } else {
+        quote! {{
+            #[allow(unused_mut)]
+            let mut properties = ::std::vec::Vec::new();
+            #[allow(unused_mut)]
+            let mut required = ::std::vec::Vec::new();
+            #(#meta_fields)*
+
+            #crate_name::registry::MetaSchema {
+                title: #title,
+                description: #description,
+                all_of: ::std::vec![
+                    #(#extends,)*
+                    #crate_name::registry::MetaSchemaRef::Inline(#crate_name::registry::MetaSchema {
+                        required,
+                        properties,
+                        ..#crate_name::registry::MetaSchema::new("object")
+                    }),
+                ],
+                deprecated: #deprecated,
+                default: #meta_object_default,
+                ..#crate_name::registry::MetaSchema::ANY
+            }
+        }}
     };
     let is_required = args.default.is_none();
 
@@ -279,12 +548,22 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 }
             }
 
+            impl #crate_name::types::IsObjectType for #ident {
+                const DENY_UNKNOWN_FIELDS: bool = #deny_unknown_fields;
+            }
+
             impl #crate_name::types::ParseFromJSON for #ident {
                 fn parse_from_json(value: #crate_name::serde_json::Value) -> ::std::result::Result<Self, #crate_name::types::ParseError<Self>> {
                     match value {
                         #crate_name::serde_json::Value::Object(obj) => {
+                            #[allow(unused_mut)]
+                            let mut errors = ::std::vec::Vec::new();
+                            #check_unknown_fields
                             #(#deserialize_fields)*
-                            ::std::result::Result::Ok(Self { #(#fields),* })
+                            if !errors.is_empty() {
+                                return ::std::result::Result::Err(#crate_name::types::ParseError::merge(errors));
+                            }
+                            ::std::result::Result::Ok(Self { #(#fields: ::std::option::Option::unwrap(#fields)),* })
                         }
                         #object_default
                         _ => ::std::result::Result::Err(#crate_name::types::ParseError::expected_type(value)),
@@ -329,8 +608,14 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 fn __internal_parse_from_json(value: #crate_name::serde_json::Value) -> ::std::result::Result<Self, #crate_name::types::ParseError<Self>> where Self: #crate_name::types::Type {
                     match value {
                         #crate_name::serde_json::Value::Object(obj) => {
+                            #[allow(unused_mut)]
+                            let mut errors = ::std::vec::Vec::new();
+                            #check_unknown_fields
                             #(#deserialize_fields)*
-                            ::std::result::Result::Ok(Self { #(#fields),* })
+                            if !errors.is_empty() {
+                                return ::std::result::Result::Err(#crate_name::types::ParseError::merge(errors));
+                            }
+                            ::std::result::Result::Ok(Self { #(#fields: ::std::option::Option::unwrap(#fields)),* })
                         }
                         #object_default
                         _ => ::std::result::Result::Err(#crate_name::types::ParseError::expected_type(value)),
@@ -373,6 +658,10 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     }
                 }
 
+                impl #crate_name::types::IsObjectType for #concrete_type {
+                    const DENY_UNKNOWN_FIELDS: bool = #deny_unknown_fields;
+                }
+
                 impl #crate_name::types::ParseFromJSON for #concrete_type {
                     fn parse_from_json(value: #crate_name::serde_json::Value) -> ::std::result::Result<Self, #crate_name::types::ParseError<Self>> {
                         Self::__internal_parse_from_json(value)
@@ -103,8 +103,11 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 let payload_ty = &values[1].ty;
                 into_responses.push(quote! {
                     #ident::#item_ident(status, payload, #(#match_headers),*) => {
-                        let mut resp = #crate_name::poem::IntoResponse::into_response(payload);
-                        resp.set_status(status);
+                        let mut resp = #crate_name::payload::ResponseContent::into_response_with_request(payload, request)?;
+                        // Keep the status set by the content, such as `206 Partial Content`.
+                        if resp.status() == #crate_name::poem::http::StatusCode::OK {
+                            resp.set_status(status);
+                        }
                         #(#with_headers)*
                         resp
                     }
@@ -113,11 +116,12 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     #crate_name::registry::MetaResponse {
                         description: #item_description,
                         status: ::std::option::Option::None,
-                        content: ::std::vec![#crate_name::registry::MetaMediaType {
-                            content_type: <#payload_ty as #crate_name::payload::Payload>::CONTENT_TYPE,
-                            schema: <#payload_ty as #crate_name::payload::Payload>::schema_ref(),
-                        }],
-                        headers: ::std::vec![#(#meta_headers),*],
+                        content: <#payload_ty as #crate_name::payload::ResponseContent>::media_types(),
+                        headers: {
+                            let mut headers = ::std::vec![#(#meta_headers),*];
+                            ::std::iter::Extend::extend(&mut headers, <#payload_ty as #crate_name::payload::ResponseContent>::headers());
+                            headers
+                        },
                     }
                 });
                 schemas.push(payload_ty);
@@ -129,8 +133,11 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 let status = get_status(variant.ident.span(), variant.status)?;
                 into_responses.push(quote! {
                     #ident::#item_ident(payload, #(#match_headers),*) => {
-                        let mut resp = #crate_name::poem::IntoResponse::into_response(payload);
-                        resp.set_status(#crate_name::poem::http::StatusCode::from_u16(#status).unwrap());
+                        let mut resp = #crate_name::payload::ResponseContent::into_response_with_request(payload, request)?;
+                        // Keep the status set by the content, such as `206 Partial Content`.
+                        if resp.status() == #crate_name::poem::http::StatusCode::OK {
+                            resp.set_status(#crate_name::poem::http::StatusCode::from_u16(#status).unwrap());
+                        }
                         #(#with_headers)*
                         resp
                     }
@@ -139,11 +146,12 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     #crate_name::registry::MetaResponse {
                         description: #item_description,
                         status: ::std::option::Option::Some(#status),
-                        content: ::std::vec![#crate_name::registry::MetaMediaType {
-                            content_type: <#payload_ty as #crate_name::payload::Payload>::CONTENT_TYPE,
-                            schema: <#payload_ty as #crate_name::payload::Payload>::schema_ref(),
-                        }],
-                        headers: ::std::vec![#(#meta_headers),*],
+                        content: <#payload_ty as #crate_name::payload::ResponseContent>::media_types(),
+                        headers: {
+                            let mut headers = ::std::vec![#(#meta_headers),*];
+                            ::std::iter::Extend::extend(&mut headers, <#payload_ty as #crate_name::payload::ResponseContent>::headers());
+                            headers
+                        },
                     }
                 });
                 schemas.push(payload_ty);
@@ -224,26 +232,60 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         quote! {
             impl #crate_name::poem::IntoResponse for #ident {
                 fn into_response(self) -> #crate_name::poem::Response {
-                    match self {
-                        #(#into_responses)*
+                    match #crate_name::Response::into_response_with_request(self, ::std::option::Option::None) {
+                        ::std::result::Result::Ok(resp) => resp,
+                        ::std::result::Result::Err(err) => #crate_name::poem::IntoResponse::into_response(err),
                     }
                 }
             }
 
             impl #crate_name::Response for #ident {
                 #bad_request_handler_const
+                const NEGOTIATED: bool = false #(|| <#schemas as #crate_name::payload::ResponseContent>::NEGOTIATED)*;
 
                 fn meta() -> #crate_name::registry::MetaResponses {
-                    #crate_name::registry::MetaResponses {
-                        responses: ::std::vec![#(#responses_meta),*],
+                    #[allow(unused_mut)]
+                    let mut responses: ::std::vec::Vec<#crate_name::registry::MetaResponse> = ::std::vec![#(#responses_meta),*];
+                    #(
+                    for resp in <#schemas as #crate_name::payload::ResponseContent>::extra_responses() {
+                        if !::std::iter::Iterator::any(&mut responses.iter(), |item| item.status == resp.status) {
+                            responses.push(resp);
+                        }
                     }
+                    )*
+                    #crate_name::registry::MetaResponses { responses }
                 }
 
                 fn register(registry: &mut #crate_name::registry::Registry) {
-                    #(<#schemas as #crate_name::payload::Payload>::register(registry);)*
+                    #(<#schemas as #crate_name::payload::ResponseContent>::register(registry);)*
                 }
 
                 #bad_request_handler
+
+                #[allow(unused_mut)]
+                fn check_accept(request: &#crate_name::poem::Request) -> ::std::result::Result<(), #crate_name::ParseRequestError> {
+                    // Only the negotiated contents are checked, because the
+                    // other ones are always sent with their own media type.
+                    // The variant is unknown before the operation is invoked,
+                    // so it passes if any of them is accepted.
+                    let mut res = ::std::result::Result::Ok(());
+                    #(
+                    if <#schemas as #crate_name::payload::ResponseContent>::NEGOTIATED {
+                        match <#schemas as #crate_name::payload::ResponseContent>::check_accept(request) {
+                            ::std::result::Result::Ok(()) => return ::std::result::Result::Ok(()),
+                            ::std::result::Result::Err(err) => res = ::std::result::Result::Err(err),
+                        }
+                    }
+                    )*
+                    res
+                }
+
+                #[allow(unused_variables)]
+                fn into_response_with_request(self, request: ::std::option::Option<&#crate_name::poem::Request>) -> ::std::result::Result<#crate_name::poem::Response, #crate_name::ParseRequestError> {
+                    ::std::result::Result::Ok(match self {
+                        #(#into_responses)*
+                    })
+                }
             }
         }
     };
@@ -1,9 +1,9 @@
 use std::convert::TryFrom;
 
 use darling::{
-    ast::{Data, Style},
-    util::{Ignored, SpannedValue},
-    FromDeriveInput, FromMeta,
+    ast::{Data, Fields, Style},
+    util::SpannedValue,
+    FromDeriveInput, FromMeta, FromVariant,
 };
 use http::header::HeaderName;
 use proc_macro2::{Ident, Span, TokenStream};
@@ -26,7 +26,7 @@ pub(crate) enum AuthType {
     Bearer,
     #[darling(rename = "oauth2")]
     OAuth2,
-    #[darling(rename = "opeid_connect")]
+    #[darling(rename = "openid_connect")]
     OpenIdConnect,
 }
 
@@ -194,19 +194,25 @@ pub(crate) enum ApiKeyInType {
     Cookie,
 }
 
+#[derive(FromVariant)]
+struct SecuritySchemeItem {
+    ident: Ident,
+    fields: Fields<syn::Type>,
+}
+
 #[derive(FromDeriveInput)]
 #[darling(attributes(oai), forward_attrs(doc))]
 struct SecuritySchemeArgs {
     ident: Ident,
-    data: Data<Ignored, syn::Type>,
+    data: Data<SecuritySchemeItem, syn::Type>,
     attrs: Vec<Attribute>,
 
     #[darling(default)]
     internal: bool,
     #[darling(default)]
     name: Option<String>,
-    #[darling(rename = "type")]
-    ty: AuthType,
+    #[darling(default, rename = "type")]
+    ty: Option<AuthType>,
     #[darling(default, rename = "in")]
     key_in: Option<ApiKeyInType>,
     #[darling(default)]
@@ -217,14 +223,18 @@ struct SecuritySchemeArgs {
     flows: Option<SpannedValue<OAuthFlows>>,
     #[darling(default)]
     openid_connect_url: Option<String>,
+    #[darling(default)]
+    check_scopes: bool,
+    #[darling(default)]
+    checker: Option<syn::Path>,
 }
 
 impl SecuritySchemeArgs {
     fn validate(&self) -> GeneratorResult<()> {
         match self.ty {
-            AuthType::ApiKey => self.validate_api_key(),
-            AuthType::OAuth2 => self.validate_oauth2(),
-            AuthType::OpenIdConnect => self.validate_openid_connect(),
+            Some(AuthType::ApiKey) => self.validate_api_key(),
+            Some(AuthType::OAuth2) => self.validate_oauth2(),
+            Some(AuthType::OpenIdConnect) => self.validate_openid_connect(),
             _ => Ok(()),
         }
     }
@@ -290,6 +300,8 @@ impl SecuritySchemeArgs {
     fn generate_register_security_scheme(
         &self,
         crate_name: &TokenStream,
+        ty: AuthType,
+        field_ty: &syn::Type,
     ) -> GeneratorResult<TokenStream> {
         let description = get_description(&self.attrs)?;
         let description = optional_literal(&description);
@@ -316,7 +328,7 @@ impl SecuritySchemeArgs {
             None => quote!(::std::option::Option::None),
         };
 
-        let ts = match self.ty {
+        let ts = match ty {
             AuthType::ApiKey => {
                 quote! {
                     registry.create_security_scheme(Self::NAME, #crate_name::registry::MetaSecurityScheme {
@@ -346,6 +358,19 @@ impl SecuritySchemeArgs {
                 }
             }
             AuthType::Bearer => {
+                // With a checker, the field is the output of the checker, so
+                // the format is taken from the extractor it accepts.
+                let bearer_format = match (&self.bearer_format, &self.checker) {
+                    (Some(bearer_format), _) => {
+                        quote!(::std::option::Option::Some(#bearer_format))
+                    }
+                    (None, Some(checker)) => {
+                        quote!(#crate_name::auth::checker_bearer_format(#checker))
+                    }
+                    (None, None) => {
+                        quote!(<#field_ty as #crate_name::auth::BearerAuthorization>::BEARER_FORMAT)
+                    }
+                };
                 quote! {
                     registry.create_security_scheme(Self::NAME, #crate_name::registry::MetaSecurityScheme {
                         ty: "http",
@@ -392,8 +417,8 @@ impl SecuritySchemeArgs {
         Ok(ts)
     }
 
-    fn generate_from_request(&self, crate_name: &TokenStream) -> TokenStream {
-        match self.ty {
+    fn generate_from_request(&self, crate_name: &TokenStream, ty: AuthType) -> TokenStream {
+        match ty {
             AuthType::ApiKey => {
                 let key_name = self.key_name.as_ref().unwrap().as_str();
                 let param_in = match self.key_in.as_ref().unwrap() {
@@ -425,16 +450,20 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
 
     let fields = match &args.data {
         Data::Struct(e) => e,
-        _ => {
+        Data::Enum(items) => return generate_enum(&args, &crate_name, &oai_typename, items),
+    };
+    let ty = match args.ty {
+        Some(ty) => ty,
+        None => {
             return Err(Error::new_spanned(
                 ident,
-                "SecurityScheme can only be applied to an struct.",
+                r#"Missing a security scheme type. #[oai(type = "...")]"#,
             )
             .into())
         }
     };
 
-    if fields.style == Style::Tuple && fields.fields.len() != 1 {
+    if fields.style != Style::Tuple || fields.fields.len() != 1 {
         return Err(Error::new_spanned(
             ident,
             "Only one unnamed field is allowed in the SecurityScheme structure.",
@@ -442,10 +471,31 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         .into());
     }
 
-    let register_security_scheme = args.generate_register_security_scheme(&crate_name)?;
-    let from_request = args.generate_from_request(&crate_name);
+    let register_security_scheme =
+        args.generate_register_security_scheme(&crate_name, ty, &fields.fields[0])?;
+    let from_request = args.generate_from_request(&crate_name, ty);
+    let from_request = match &args.checker {
+        Some(checker) => quote! {
+            let output = #from_request?;
+            let output = #crate_name::auth::CheckerOutput::into_result(#checker(req, output).await)?;
+            ::std::result::Result::Ok(Self(output))
+        },
+        None => quote!(#from_request.map(Self)),
+    };
+    let check_scopes = if args.check_scopes {
+        Some(quote! {
+            const CHECK_SCOPES: bool = true;
+
+            fn check_scopes(&self, scopes: &[&'static str]) -> ::std::result::Result<(), #crate_name::ParseRequestError> {
+                #crate_name::auth::ScopeChecker::check_scopes(self, scopes)
+            }
+        })
+    } else {
+        None
+    };
 
     let expanded = quote! {
+        #[#crate_name::poem::async_trait]
         impl #crate_name::SecurityScheme for #ident {
             const NAME: &'static str = #oai_typename;
 
@@ -453,8 +503,93 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 #register_security_scheme
             }
 
-            fn from_request(req: &#crate_name::poem::Request, query: &::std::collections::HashMap<::std::string::String, ::std::string::String>) -> ::std::result::Result<Self, #crate_name::ParseRequestError> {
-                #from_request.map(Self)
+            async fn from_request(req: &#crate_name::poem::Request, query: &::std::collections::HashMap<::std::string::String, ::std::string::String>) -> ::std::result::Result<Self, #crate_name::ParseRequestError> {
+                #from_request
+            }
+
+            #check_scopes
+        }
+    };
+
+    Ok(expanded)
+}
+
+fn generate_enum(
+    args: &SecuritySchemeArgs,
+    crate_name: &TokenStream,
+    oai_typename: &str,
+    items: &[SecuritySchemeItem],
+) -> GeneratorResult<TokenStream> {
+    let ident = &args.ident;
+
+    if args.ty.is_some() || args.checker.is_some() || args.check_scopes {
+        return Err(Error::new_spanned(
+            ident,
+            "The `type`, `checker` and `check_scopes` attributes must be specified on the security schemes of the variants.",
+        )
+        .into());
+    }
+
+    let mut item_idents = Vec::new();
+    let mut item_tys = Vec::new();
+    for item in items {
+        if item.fields.style != Style::Tuple || item.fields.fields.len() != 1 {
+            return Err(Error::new_spanned(
+                &item.ident,
+                "Only one unnamed field is allowed in the SecurityScheme variant.",
+            )
+            .into());
+        }
+        item_idents.push(&item.ident);
+        item_tys.push(&item.fields.fields[0]);
+    }
+
+    let expanded = quote! {
+        #[#crate_name::poem::async_trait]
+        impl #crate_name::SecurityScheme for #ident {
+            const NAME: &'static str = #oai_typename;
+            const CHECK_SCOPES: bool = false #(|| <#item_tys as #crate_name::SecurityScheme>::CHECK_SCOPES)*;
+
+            fn register(registry: &mut #crate_name::registry::Registry) {
+                #(<#item_tys as #crate_name::SecurityScheme>::register(registry);)*
+            }
+
+            fn security_schemes() -> ::std::vec::Vec<&'static str> {
+                let mut names = ::std::vec::Vec::new();
+                #(::std::iter::Extend::extend(&mut names, <#item_tys as #crate_name::SecurityScheme>::security_schemes());)*
+                names
+            }
+
+            async fn from_request(req: &#crate_name::poem::Request, query: &::std::collections::HashMap<::std::string::String, ::std::string::String>) -> ::std::result::Result<Self, #crate_name::ParseRequestError> {
+                let mut error: ::std::option::Option<#crate_name::ParseRequestError> = ::std::option::Option::None;
+                #(
+                    match <#item_tys as #crate_name::SecurityScheme>::from_request(req, query).await {
+                        ::std::result::Result::Ok(value) => return ::std::result::Result::Ok(Self::#item_idents(value)),
+                        ::std::result::Result::Err(#crate_name::ParseRequestError::Authorization) => {}
+                        ::std::result::Result::Err(err) => {
+                            if error.is_none() {
+                                error = ::std::option::Option::Some(err);
+                            }
+                        }
+                    }
+                )*
+                ::std::result::Result::Err(error.unwrap_or(#crate_name::ParseRequestError::Authorization))
+            }
+
+            fn check_scopes(&self, scopes: &[&'static str]) -> ::std::result::Result<(), #crate_name::ParseRequestError> {
+                // A variant that does not check the scopes cannot grant them.
+                match self {
+                    #(
+                    Self::#item_idents(value) => {
+                        if !<#item_tys as #crate_name::SecurityScheme>::CHECK_SCOPES && !scopes.is_empty() {
+                            return ::std::result::Result::Err(#crate_name::ParseRequestError::InsufficientScope {
+                                scopes: ::std::iter::Iterator::collect(::std::iter::Iterator::map(scopes.iter(), |scope| ::std::string::ToString::to_string(scope))),
+                            });
+                        }
+                        <#item_tys as #crate_name::SecurityScheme>::check_scopes(value, scopes)
+                    }
+                    )*
+                }
             }
         }
     };
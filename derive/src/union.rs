@@ -0,0 +1,441 @@
+use darling::{
+    ast::{Data, Fields, Style},
+    util::Ignored,
+    FromDeriveInput, FromField, FromVariant,
+};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{ext::IdentExt, Attribute, DeriveInput, Error, Type};
+
+use crate::{
+    common_args::RenameTarget,
+    error::GeneratorResult,
+    utils::{get_crate_name, get_summary_and_description, optional_literal},
+};
+
+#[derive(FromField)]
+#[darling(attributes(oai), forward_attrs(doc))]
+struct UnionField {
+    ident: Option<Ident>,
+    ty: Type,
+    attrs: Vec<Attribute>,
+
+    #[darling(default)]
+    name: Option<String>,
+}
+
+#[derive(FromVariant)]
+#[darling(attributes(oai), forward_attrs(doc))]
+struct UnionItem {
+    ident: Ident,
+    attrs: Vec<Attribute>,
+    fields: Fields<UnionField>,
+
+    #[darling(default)]
+    mapping: Option<String>,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(oai), forward_attrs(doc))]
+struct UnionArgs {
+    ident: Ident,
+    attrs: Vec<Attribute>,
+    data: Data<UnionItem, Ignored>,
+
+    #[darling(default)]
+    internal: bool,
+    #[darling(default)]
+    name: Option<String>,
+    #[darling(default)]
+    discriminator_name: Option<String>,
+    #[darling(default)]
+    any_of: bool,
+}
+
+pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
+    let args: UnionArgs = UnionArgs::from_derive_input(&args)?;
+    let crate_name = get_crate_name(args.internal);
+    let ident = &args.ident;
+    let oai_typename = args
+        .name
+        .clone()
+        .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
+    let (title, description) = get_summary_and_description(&args.attrs)?;
+    let title = optional_literal(&title);
+    let description = optional_literal(&description);
+    let e = match &args.data {
+        Data::Enum(e) => e,
+        _ => return Err(Error::new_spanned(ident, "Union can only be applied to an enum.").into()),
+    };
+
+    let mut types = Vec::new();
+    let mut schemas = Vec::new();
+    let mut from_json = Vec::new();
+    let mut to_json = Vec::new();
+    let mut mapping = Vec::new();
+    let mut variant_schemas = Vec::new();
+
+    for variant in e {
+        let item_ident = &variant.ident;
+
+        if variant.mapping.is_some() && args.discriminator_name.is_none() {
+            return Err(Error::new_spanned(
+                &variant.ident,
+                r#"The mapping requires a discriminator name. #[oai(discriminator_name = "...")]"#,
+            )
+            .into());
+        }
+
+        match (variant.fields.style, variant.fields.fields.as_slice()) {
+            (Style::Tuple, [object_field]) => {
+                let object_ty = &object_field.ty;
+                let mapping_name = match &variant.mapping {
+                    Some(mapping) => quote!(#mapping),
+                    None => quote!({
+                        match <#object_ty as #crate_name::types::Type>::NAME {
+                            #crate_name::types::TypeName::Normal { ty, .. } => ty,
+                            _ => ::std::unreachable!(),
+                        }
+                    }),
+                };
+
+                types.push(object_ty);
+
+                match &args.discriminator_name {
+                    Some(discriminator_name) => {
+                        // Each variant is documented as a schema that extends the object
+                        // type with the discriminator property.
+                        let variant_schema_name = format!("{}_{}", oai_typename, item_ident);
+
+                        schemas.push(quote!(#crate_name::registry::MetaSchemaRef::Reference(#variant_schema_name)));
+                        variant_schemas.push(quote! {
+                            {
+                                fn assert_object_type<T: #crate_name::types::IsObjectType>() {}
+                                assert_object_type::<#object_ty>();
+                            }
+                            registry.create_schema(#variant_schema_name, |_| #crate_name::registry::MetaSchema {
+                                all_of: ::std::vec![
+                                    <#object_ty as #crate_name::types::Type>::schema_ref(),
+                                    #crate_name::registry::MetaSchemaRef::Inline(#crate_name::registry::MetaSchema {
+                                        required: ::std::vec![#discriminator_name],
+                                        properties: ::std::vec![(
+                                            #discriminator_name,
+                                            #crate_name::registry::MetaSchemaRef::Inline(#crate_name::registry::MetaSchema {
+                                                enum_items: ::std::vec![#crate_name::serde_json::Value::String(::std::string::ToString::to_string(#mapping_name))],
+                                                ..#crate_name::registry::MetaSchema::new("string")
+                                            }),
+                                        )],
+                                        ..#crate_name::registry::MetaSchema::new("object")
+                                    }),
+                                ],
+                                ..#crate_name::registry::MetaSchema::ANY
+                            });
+                        });
+                        from_json.push(quote! {
+                            ::std::option::Option::Some(discriminator) if discriminator == #mapping_name => {
+                                let mut value = value;
+                                if let #crate_name::serde_json::Value::Object(obj) = &mut value {
+                                    obj.remove(#discriminator_name);
+                                }
+                                <#object_ty as #crate_name::types::ParseFromJSON>::parse_from_json(value)
+                                    .map(Self::#item_ident)
+                                    .map_err(#crate_name::types::ParseError::propagate)
+                            }
+                        });
+                        to_json.push(quote! {
+                            Self::#item_ident(obj) => {
+                                let mut value = <#object_ty as #crate_name::types::ToJSON>::to_json(obj);
+                                if let #crate_name::serde_json::Value::Object(obj) = &mut value {
+                                    obj.insert(
+                                        ::std::string::ToString::to_string(#discriminator_name),
+                                        #crate_name::serde_json::Value::String(::std::string::ToString::to_string(#mapping_name)),
+                                    );
+                                }
+                                value
+                            }
+                        });
+                        mapping.push(quote! {
+                            (
+                                ::std::string::ToString::to_string(#mapping_name),
+                                ::std::string::ToString::to_string(::std::concat!("#/components/schemas/", #variant_schema_name)),
+                            )
+                        });
+                    }
+                    None => {
+                        schemas
+                            .push(quote!(<#object_ty as #crate_name::types::Type>::schema_ref()));
+                        from_json.push(quote! {
+                            if let ::std::result::Result::Ok(obj) = <#object_ty as #crate_name::types::ParseFromJSON>::parse_from_json(::std::clone::Clone::clone(&value)) {
+                                return ::std::result::Result::Ok(Self::#item_ident(obj));
+                            }
+                        });
+                        to_json.push(quote! {
+                            Self::#item_ident(obj) => <#object_ty as #crate_name::types::ToJSON>::to_json(obj)
+                        });
+                    }
+                }
+            }
+            (Style::Struct, variant_fields) => {
+                // A struct variant is documented as an inline object schema with a
+                // property for each field.
+                let mapping_name = variant
+                    .mapping
+                    .clone()
+                    .unwrap_or_else(|| item_ident.unraw().to_string());
+                let (variant_title, variant_description) =
+                    get_summary_and_description(&variant.attrs)?;
+                let variant_title = optional_literal(&variant_title);
+                let variant_description = optional_literal(&variant_description);
+                let mut field_idents = Vec::new();
+                let mut field_names = Vec::new();
+                let mut meta_fields = Vec::new();
+                let mut deserialize_fields = Vec::new();
+
+                for field in variant_fields {
+                    let field_ident = field.ident.as_ref().unwrap();
+                    let field_ty = &field.ty;
+                    let field_name = field.name.clone().unwrap_or_else(|| {
+                        RenameTarget::Field.rename(field_ident.unraw().to_string())
+                    });
+                    let (field_title, field_description) =
+                        get_summary_and_description(&field.attrs)?;
+                    let field_title = optional_literal(&field_title);
+                    let field_description = optional_literal(&field_description);
+
+                    meta_fields.push(quote! {
+                        properties.push({
+                            <#field_ty as #crate_name::types::Type>::register(registry);
+
+                            let mut schema_ref = <#field_ty as #crate_name::types::Type>::schema_ref();
+
+                            if let #crate_name::registry::MetaSchemaRef::Inline(schema) = &mut schema_ref {
+                                if let ::std::option::Option::Some(title) = #field_title {
+                                    schema.title = ::std::option::Option::Some(title);
+                                }
+
+                                if let ::std::option::Option::Some(field_description) = #field_description {
+                                    schema.description = ::std::option::Option::Some(field_description);
+                                }
+                            }
+
+                            (#field_name, schema_ref)
+                        });
+
+                        if <#field_ty as #crate_name::types::Type>::IS_REQUIRED {
+                            required.push(#field_name);
+                        }
+                    });
+                    deserialize_fields.push(quote! {
+                        #[allow(non_snake_case)]
+                        let #field_ident: ::std::option::Option<#field_ty> = match #crate_name::types::ParseFromJSON::parse_from_json(obj.get(#field_name).cloned().unwrap_or_default()) {
+                            ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                            ::std::result::Result::Err(err) => {
+                                errors.push(#crate_name::types::ParseError::propagate_field(err, #field_name));
+                                ::std::option::Option::None
+                            }
+                        };
+                    });
+                    field_idents.push(field_ident);
+                    field_names.push(field_name);
+                }
+
+                let (discriminator_meta, insert_discriminator) = match &args.discriminator_name {
+                    Some(discriminator_name) => (
+                        quote! {
+                            required.push(#discriminator_name);
+                            properties.push((
+                                #discriminator_name,
+                                #crate_name::registry::MetaSchemaRef::Inline(#crate_name::registry::MetaSchema {
+                                    enum_items: ::std::vec![#crate_name::serde_json::Value::String(::std::string::ToString::to_string(#mapping_name))],
+                                    ..#crate_name::registry::MetaSchema::new("string")
+                                }),
+                            ));
+                        },
+                        quote! {
+                            object.insert(
+                                ::std::string::ToString::to_string(#discriminator_name),
+                                #crate_name::serde_json::Value::String(::std::string::ToString::to_string(#mapping_name)),
+                            );
+                        },
+                    ),
+                    None => (quote!(), quote!()),
+                };
+                let variant_meta = quote! {{
+                    #[allow(unused_mut)]
+                    let mut properties = ::std::vec::Vec::new();
+                    #[allow(unused_mut)]
+                    let mut required = ::std::vec::Vec::new();
+                    #discriminator_meta
+                    #(#meta_fields)*
+
+                    #crate_name::registry::MetaSchema {
+                        title: #variant_title,
+                        description: #variant_description,
+                        required,
+                        properties,
+                        ..#crate_name::registry::MetaSchema::new("object")
+                    }
+                }};
+                let parse_variant = quote! {
+                    (|| -> #crate_name::types::ParseResult<Self> {
+                        let obj = match value {
+                            #crate_name::serde_json::Value::Object(obj) => obj,
+                            value => return ::std::result::Result::Err(#crate_name::types::ParseError::expected_type(value)),
+                        };
+                        #[allow(unused_mut)]
+                        let mut errors = ::std::vec::Vec::new();
+                        #(#deserialize_fields)*
+                        if !errors.is_empty() {
+                            return ::std::result::Result::Err(#crate_name::types::ParseError::merge(errors));
+                        }
+                        ::std::result::Result::Ok(Self::#item_ident { #(#field_idents: ::std::option::Option::unwrap(#field_idents)),* })
+                    })()
+                };
+
+                to_json.push(quote! {
+                    Self::#item_ident { #(#field_idents),* } => {
+                        let mut object = #crate_name::serde_json::Map::new();
+                        #(object.insert(::std::string::ToString::to_string(#field_names), #crate_name::types::ToJSON::to_json(#field_idents));)*
+                        #insert_discriminator
+                        #crate_name::serde_json::Value::Object(object)
+                    }
+                });
+
+                match &args.discriminator_name {
+                    Some(discriminator_name) => {
+                        let variant_schema_name = format!("{}_{}", oai_typename, item_ident);
+
+                        schemas.push(quote!(#crate_name::registry::MetaSchemaRef::Reference(#variant_schema_name)));
+                        variant_schemas.push(quote! {
+                            registry.create_schema(#variant_schema_name, |registry| #variant_meta);
+                        });
+                        from_json.push(quote! {
+                            ::std::option::Option::Some(discriminator) if discriminator == #mapping_name => {
+                                let mut value = value;
+                                if let #crate_name::serde_json::Value::Object(obj) = &mut value {
+                                    obj.remove(#discriminator_name);
+                                }
+                                #parse_variant
+                            }
+                        });
+                        mapping.push(quote! {
+                            (
+                                ::std::string::ToString::to_string(#mapping_name),
+                                ::std::string::ToString::to_string(::std::concat!("#/components/schemas/", #variant_schema_name)),
+                            )
+                        });
+                    }
+                    None => {
+                        schemas.push(
+                            quote!(#crate_name::registry::MetaSchemaRef::Inline(#variant_meta)),
+                        );
+                        from_json.push(quote! {
+                            if let ::std::result::Result::Ok(obj) = {
+                                let value = ::std::clone::Clone::clone(&value);
+                                #parse_variant
+                            } {
+                                return ::std::result::Result::Ok(obj);
+                            }
+                        });
+                    }
+                }
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    &variant.ident,
+                    format!(
+                        "Invalid union variant {}.\nOpenAPI unions may only contain \
+                         newtype or struct variants.",
+                        variant.ident
+                    ),
+                )
+                .into())
+            }
+        }
+    }
+
+    let parse_from_json = match &args.discriminator_name {
+        Some(discriminator_name) => quote! {
+            let discriminator = value
+                .as_object()
+                .and_then(|obj| obj.get(#discriminator_name))
+                .and_then(#crate_name::serde_json::Value::as_str)
+                .map(::std::string::ToString::to_string);
+            match discriminator.as_deref() {
+                #(#from_json,)*
+                _ => ::std::result::Result::Err(#crate_name::types::ParseError::expected_type(value)),
+            }
+        },
+        None => quote! {
+            #(#from_json)*
+            ::std::result::Result::Err(#crate_name::types::ParseError::expected_type(value))
+        },
+    };
+
+    let discriminator = match &args.discriminator_name {
+        Some(discriminator_name) => quote! {
+            ::std::option::Option::Some(#crate_name::registry::MetaDiscriminatorObject {
+                property_name: #discriminator_name,
+                mapping: ::std::vec![#(#mapping),*],
+            })
+        },
+        None => quote!(::std::option::Option::None),
+    };
+
+    let (one_of, any_of) = if args.any_of {
+        (quote!(::std::vec![]), quote!(::std::vec![#(#schemas),*]))
+    } else {
+        (quote!(::std::vec![#(#schemas),*]), quote!(::std::vec![]))
+    };
+
+    let expanded = quote! {
+        impl #crate_name::types::Type for #ident {
+            const NAME: #crate_name::types::TypeName = #crate_name::types::TypeName::Normal {
+                ty: #oai_typename,
+                format: ::std::option::Option::None,
+            };
+
+            type ValueType = Self;
+
+            fn as_value(&self) -> ::std::option::Option<&Self> {
+                ::std::option::Option::Some(self)
+            }
+
+            fn schema_ref() -> #crate_name::registry::MetaSchemaRef {
+                #crate_name::registry::MetaSchemaRef::Reference(#oai_typename)
+            }
+
+            fn register(registry: &mut #crate_name::registry::Registry) {
+                registry.create_schema(#oai_typename, |registry| {
+                    #(<#types as #crate_name::types::Type>::register(registry);)*
+                    #(#variant_schemas)*
+
+                    #crate_name::registry::MetaSchema {
+                        title: #title,
+                        description: #description,
+                        one_of: #one_of,
+                        any_of: #any_of,
+                        discriminator: #discriminator,
+                        ..#crate_name::registry::MetaSchema::ANY
+                    }
+                });
+            }
+        }
+
+        impl #crate_name::types::ParseFromJSON for #ident {
+            fn parse_from_json(value: #crate_name::serde_json::Value) -> #crate_name::types::ParseResult<Self> {
+                #parse_from_json
+            }
+        }
+
+        impl #crate_name::types::ToJSON for #ident {
+            fn to_json(&self) -> #crate_name::serde_json::Value {
+                match self {
+                    #(#to_json),*
+                }
+            }
+        }
+    };
+
+    Ok(expanded)
+}
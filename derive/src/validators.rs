@@ -93,50 +93,72 @@ impl<'a> Validators<'a> {
         Ok(validators)
     }
 
-    pub(crate) fn create_obj_field_checker(
+    fn create_checker(
         &self,
         crate_name: &TokenStream,
-        field_name: &str,
+        pointer: TokenStream,
+        actual: TokenStream,
+        on_failed: TokenStream,
     ) -> GeneratorResult<Option<TokenStream>> {
         let validators = self.create_validators(crate_name)?;
-        if !validators.is_empty() {
-            Ok(Some(quote! {
-                for validator in [#(#validators),*] {
-                    if let ::std::option::Option::Some(value) = #crate_name::types::Type::as_value(&value) {
-                        if !#crate_name::validation::Validator::check(&validator, value) {
-                            return Err(#crate_name::types::ParseError::<Self>::custom(format!("field `{}` verification failed. {}", #field_name, validator)));
-                        }
+        if validators.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(quote! {
+            let mut failed = ::std::vec::Vec::<::std::string::String>::new();
+            let mut violations = ::std::vec::Vec::<#crate_name::Violation>::new();
+            #({
+                let validator = #validators;
+                if let ::std::option::Option::Some(value) = #crate_name::types::Type::as_value(&value) {
+                    if !#crate_name::validation::Validator::check(&validator, value) {
+                        failed.push(::std::string::ToString::to_string(&validator));
+                        violations.push(#crate_name::validation::violation(&validator, #pointer, #actual));
                     }
                 }
-            }))
-        } else {
-            Ok(None)
-        }
+            })*
+            if !violations.is_empty() {
+                let failed = failed.join(", ");
+                #on_failed
+            }
+        }))
+    }
+
+    pub(crate) fn create_obj_field_checker(
+        &self,
+        crate_name: &TokenStream,
+        field_name: &str,
+    ) -> GeneratorResult<Option<TokenStream>> {
+        let pointer = format!("/{}", field_name.replace('~', "~0").replace('/', "~1"));
+        self.create_checker(
+            crate_name,
+            quote!(::std::string::ToString::to_string(#pointer)),
+            quote!(obj.get(#field_name).cloned()),
+            quote! {
+                return Err(#crate_name::types::ParseError::<Self>::custom(format!("field `{}` verification failed. {}", #field_name, failed)).with_violations(violations));
+            },
+        )
     }
 
     pub(crate) fn create_param_checker(
         &self,
         crate_name: &TokenStream,
         arg_name: &str,
+        actual: TokenStream,
     ) -> GeneratorResult<Option<TokenStream>> {
-        let validators = self.create_validators(crate_name)?;
-        if !validators.is_empty() {
-            Ok(Some(quote! {
-                for validator in [#(#validators),*] {
-                    if let ::std::option::Option::Some(value) = #crate_name::types::Type::as_value(&value) {
-                        if !#crate_name::validation::Validator::check(&validator, value) {
-                            let err = #crate_name::ParseRequestError::ParseParam {
-                                name: #arg_name,
-                                reason: ::std::format!("verification failed. {}", validator),
-                            };
-                            return Err(#crate_name::poem::Error::bad_request(err));
-                        }
-                    }
-                }
-            }))
-        } else {
-            Ok(None)
-        }
+        self.create_checker(
+            crate_name,
+            quote!(::std::string::String::new()),
+            actual,
+            quote! {
+                let err = #crate_name::ParseRequestError::ParseParam {
+                    name: #arg_name,
+                    reason: ::std::format!("verification failed. {}", failed),
+                    violations,
+                };
+                return Err(err);
+            },
+        )
     }
 
     pub(crate) fn create_multipart_field_checker(
@@ -144,22 +166,18 @@ impl<'a> Validators<'a> {
         crate_name: &TokenStream,
         field_name: &str,
     ) -> GeneratorResult<Option<TokenStream>> {
-        let validators = self.create_validators(crate_name)?;
-        if !validators.is_empty() {
-            Ok(Some(quote! {
-                for validator in [#(#validators),*] {
-                    if let ::std::option::Option::Some(value) = #crate_name::types::Type::as_value(&value) {
-                        if !#crate_name::validation::Validator::check(&validator, value) {
-                            return Err(#crate_name::ParseRequestError::ParseRequestBody {
-                                reason: ::std::format!("field `{}` verification failed. {}", #field_name, validator),
-                            });
-                        }
-                    }
-                }
-            }))
-        } else {
-            Ok(None)
-        }
+        let pointer = format!("/{}", field_name.replace('~', "~0").replace('/', "~1"));
+        self.create_checker(
+            crate_name,
+            quote!(::std::string::ToString::to_string(#pointer)),
+            quote!(::std::option::Option::None),
+            quote! {
+                return Err(#crate_name::ParseRequestError::ParseRequestBody {
+                    reason: ::std::format!("field `{}` verification failed. {}", #field_name, failed),
+                    violations,
+                });
+            },
+        )
     }
 
     pub(crate) fn create_update_meta(
@@ -169,9 +187,7 @@ impl<'a> Validators<'a> {
         let validators = self.create_validators(crate_name)?;
         if !validators.is_empty() {
             Ok(Some(quote! {
-                for validator in [#(#validators),*] {
-                    #crate_name::validation::ValidatorMeta::update_meta(&validator, schema);
-                }
+                #(#crate_name::validation::ValidatorMeta::update_meta(&#validators, schema);)*
             }))
         } else {
             Ok(None)
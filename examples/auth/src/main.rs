@@ -59,7 +59,7 @@ impl Api {
     #[oai(path = "/oauth2", method = "get")]
     async fn auth_oauth2(
         &self,
-        #[oai(auth("public_repo", "read:user"))] auth: GithubAuthorization,
+        #[oai(auth)] auth: GithubAuthorization,
     ) -> Result<PlainText> {
         let client = reqwest::Client::new();
         let text = client
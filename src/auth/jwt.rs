@@ -0,0 +1,345 @@
+use std::{io::Error as IoError, marker::PhantomData, path::Path, sync::Arc};
+
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{AlgorithmParameters, Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use poem::Request;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::{
+    auth::{Bearer, BearerAuthorization},
+    ParseRequestError,
+};
+
+/// An error that occurs when loading a key used to verify JWTs.
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+#[derive(Debug, Error)]
+pub enum JwtKeyError {
+    /// Failed to read the key set file.
+    #[error("failed to read the key set: {0}")]
+    Io(#[from] IoError),
+
+    /// The key or the key set is invalid.
+    #[error("invalid key: {0}")]
+    InvalidKey(String),
+}
+
+impl From<jsonwebtoken::errors::Error> for JwtKeyError {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        JwtKeyError::InvalidKey(err.to_string())
+    }
+}
+
+/// A key used to verify the signature of JWTs.
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+#[derive(Clone)]
+pub struct JwtKey {
+    kid: Option<String>,
+    algorithm: Option<Algorithm>,
+    key: DecodingKey,
+}
+
+impl JwtKey {
+    fn new(key: DecodingKey) -> Self {
+        Self {
+            kid: None,
+            algorithm: None,
+            key,
+        }
+    }
+
+    /// Create a key from the secret of `HS256`, `HS384` or `HS512`.
+    pub fn hmac(secret: impl AsRef<[u8]>) -> Self {
+        Self::new(DecodingKey::from_secret(secret.as_ref()))
+    }
+
+    /// Create a key from the PEM encoded public key of `RS*` or `PS*`.
+    pub fn rsa_pem(pem: impl AsRef<[u8]>) -> Result<Self, JwtKeyError> {
+        Ok(Self::new(DecodingKey::from_rsa_pem(pem.as_ref())?))
+    }
+
+    /// Create a key from the PEM encoded public key of `ES256` or `ES384`.
+    pub fn ec_pem(pem: impl AsRef<[u8]>) -> Result<Self, JwtKeyError> {
+        Ok(Self::new(DecodingKey::from_ec_pem(pem.as_ref())?))
+    }
+
+    /// Create a key from a JSON Web Key, the `kid` and `alg` parameters of
+    /// the key are respected.
+    fn from_jwk(jwk: &Jwk) -> Result<Self, JwtKeyError> {
+        let key = match &jwk.algorithm {
+            // The `k` parameter is base64url encoded without padding.
+            AlgorithmParameters::OctetKey(params) => DecodingKey::from_secret(
+                &base64::decode_config(&params.value, base64::URL_SAFE_NO_PAD)
+                    .map_err(|err| JwtKeyError::InvalidKey(err.to_string()))?,
+            ),
+            _ => DecodingKey::from_jwk(jwk)?,
+        };
+        Ok(Self {
+            kid: jwk.common.key_id.clone(),
+            algorithm: jwk.common.algorithm,
+            key,
+        })
+    }
+
+    /// Set the key id, the key is only used to verify the tokens whose `kid`
+    /// header matches it.
+    #[must_use]
+    pub fn kid(self, kid: impl Into<String>) -> Self {
+        Self {
+            kid: Some(kid.into()),
+            ..self
+        }
+    }
+
+    fn matches(&self, kid: Option<&str>, algorithm: Algorithm) -> bool {
+        let kid_matched = match (kid, &self.kid) {
+            (Some(kid), Some(key_kid)) => kid == key_kid,
+            _ => true,
+        };
+        let algorithm_matched = match self.algorithm {
+            Some(alg) => alg == algorithm,
+            None => true,
+        };
+        kid_matched && algorithm_matched
+    }
+}
+
+#[derive(Clone, Default)]
+struct JwtKeysInner {
+    keys: Vec<JwtKey>,
+    issuers: Vec<String>,
+    audiences: Vec<String>,
+    leeway: Option<u64>,
+}
+
+/// The keys and the rules used by [`Jwt`] to verify tokens.
+///
+/// It must be added to the endpoint with the
+/// [`AddData`](poem::middleware::AddData) middleware. `S` is a marker type
+/// that selects the security schemes using the key set, the key set created
+/// by [`JwtKeys::new`] is used by `Jwt<T>`, and the one created by
+/// [`JwtKeys::for_scheme`] is only used by `Jwt<T, S>`.
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+pub struct JwtKeys<S = ()> {
+    inner: Arc<JwtKeysInner>,
+    _mark: PhantomData<fn() -> S>,
+}
+
+impl<S> Clone for JwtKeys<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _mark: PhantomData,
+        }
+    }
+}
+
+impl<S> Default for JwtKeys<S> {
+    fn default() -> Self {
+        Self {
+            inner: Default::default(),
+            _mark: PhantomData,
+        }
+    }
+}
+
+impl JwtKeys {
+    /// Create an empty key set.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create an empty key set used by the security schemes whose extractor
+    /// is `Jwt<T, S>`.
+    pub fn for_scheme<S>() -> JwtKeys<S> {
+        Default::default()
+    }
+}
+
+impl<S> JwtKeys<S> {
+    /// Add a key.
+    #[must_use]
+    pub fn key(mut self, key: JwtKey) -> Self {
+        Arc::make_mut(&mut self.inner).keys.push(key);
+        self
+    }
+
+    /// Add all the keys of a JSON Web Key Set.
+    pub fn jwks(mut self, jwks: &str) -> Result<Self, JwtKeyError> {
+        let jwks = serde_json::from_str::<JwkSet>(jwks)
+            .map_err(|err| JwtKeyError::InvalidKey(err.to_string()))?;
+        for jwk in &jwks.keys {
+            let key = JwtKey::from_jwk(jwk)?;
+            Arc::make_mut(&mut self.inner).keys.push(key);
+        }
+        Ok(self)
+    }
+
+    /// Add all the keys of a JSON Web Key Set file.
+    pub fn jwks_file(self, path: impl AsRef<Path>) -> Result<Self, JwtKeyError> {
+        let jwks = std::fs::read_to_string(path)?;
+        self.jwks(&jwks)
+    }
+
+    /// Add an accepted issuer, the `iss` claim is required if any issuer is
+    /// specified.
+    #[must_use]
+    pub fn issuer(mut self, issuer: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner).issuers.push(issuer.into());
+        self
+    }
+
+    /// Add an accepted audience, the `aud` claim is required if any audience
+    /// is specified.
+    #[must_use]
+    pub fn audience(mut self, audience: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner)
+            .audiences
+            .push(audience.into());
+        self
+    }
+
+    /// Set the leeway in seconds when checking the `exp` and `nbf` claims,
+    /// defaults to `60`.
+    #[must_use]
+    pub fn leeway(mut self, seconds: u64) -> Self {
+        Arc::make_mut(&mut self.inner).leeway = Some(seconds);
+        self
+    }
+
+    fn validation(&self, algorithm: Algorithm) -> Validation {
+        let mut validation = Validation::new(algorithm);
+        validation.validate_nbf = true;
+        if let Some(leeway) = self.inner.leeway {
+            validation.leeway = leeway;
+        }
+        if !self.inner.issuers.is_empty() {
+            validation.set_issuer(&self.inner.issuers);
+            validation.required_spec_claims.insert("iss".to_string());
+        }
+        if !self.inner.audiences.is_empty() {
+            validation.set_audience(&self.inner.audiences);
+            validation.required_spec_claims.insert("aud".to_string());
+        }
+        validation
+    }
+
+    /// Returns the key set of the security scheme `S` added to the endpoint.
+    pub(crate) fn from_request(req: &Request) -> Result<&Self, ParseRequestError>
+    where
+        S: 'static,
+    {
+        req.extensions().get::<Self>().ok_or_else(|| {
+            ParseRequestError::SecuritySchemeNotConfigured(format!(
+                "the key set `{}` is not added to the endpoint.",
+                std::any::type_name::<Self>()
+            ))
+        })
+    }
+
+    /// Returns `true` if both the accepted issuers and audiences are
+    /// specified.
+    pub(crate) fn has_issuer_and_audience(&self) -> bool {
+        !self.inner.issuers.is_empty() && !self.inner.audiences.is_empty()
+    }
+
+    pub(crate) fn decode<T: DeserializeOwned>(&self, token: &str) -> Option<T> {
+        let header = decode_header(token).ok()?;
+        let validation = self.validation(header.alg);
+        self.inner
+            .keys
+            .iter()
+            .filter(|key| key.matches(header.kid.as_deref(), header.alg))
+            .find_map(|key| decode::<T>(token, &key.key, &validation).ok())
+            .map(|data| data.claims)
+    }
+}
+
+/// Used to extract and verify a JSON Web Token from the request.
+///
+/// The signature is verified with the [`JwtKeys<S>`](JwtKeys) added to the
+/// endpoint, the `exp` and `nbf` claims are checked, and so are the `iss` and
+/// `aud` claims if the accepted values are specified. The security scheme
+/// uses `JWT` as its bearer format. A `500 Internal Server Error` response is
+/// returned if the key set is not added to the endpoint.
+///
+/// Several security schemes can use different key sets by using themselves as
+/// the marker type `S`, such as `struct AdminAuth(Jwt<Claims, AdminAuth>)`
+/// with the key set created by `JwtKeys::for_scheme::<AdminAuth>()`.
+///
+/// # Examples
+///
+/// ```
+/// use poem::{middleware::AddData, EndpointExt, IntoEndpoint};
+/// use poem_openapi::{
+///     auth::{Jwt, JwtKey, JwtKeys},
+///     OpenApi, OpenApiService, SecurityScheme,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Claims {
+///     sub: String,
+/// }
+///
+/// #[derive(SecurityScheme)]
+/// #[oai(type = "bearer")]
+/// struct MyAuth(Jwt<Claims>);
+///
+/// #[derive(SecurityScheme)]
+/// #[oai(type = "bearer")]
+/// struct AdminAuth(Jwt<Claims, AdminAuth>);
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/me", method = "get")]
+///     async fn me(&self, #[oai(auth)] auth: MyAuth) {
+///         println!("{}", auth.0.claims.sub);
+///     }
+///
+///     #[oai(path = "/admin", method = "get")]
+///     async fn admin(&self, #[oai(auth)] auth: AdminAuth) {
+///         println!("{}", auth.0.claims.sub);
+///     }
+/// }
+///
+/// let keys = JwtKeys::new()
+///     .key(JwtKey::hmac("secret"))
+///     .issuer("https://example.com");
+/// let admin_keys = JwtKeys::for_scheme::<AdminAuth>()
+///     .key(JwtKey::hmac("admin-secret"))
+///     .issuer("https://example.com");
+/// let app = OpenApiService::new(Api)
+///     .into_endpoint()
+///     .with(AddData::new(keys))
+///     .with(AddData::new(admin_keys));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+pub struct Jwt<T, S = ()> {
+    /// The raw token.
+    pub token: String,
+    /// The claims of the token.
+    pub claims: T,
+    _mark: PhantomData<fn() -> S>,
+}
+
+impl<T: DeserializeOwned, S: 'static> BearerAuthorization for Jwt<T, S> {
+    const BEARER_FORMAT: Option<&'static str> = Some("JWT");
+
+    fn from_request(req: &Request) -> Result<Self, ParseRequestError> {
+        let Bearer { token } = Bearer::from_request(req)?;
+        let claims = JwtKeys::<S>::from_request(req)?
+            .decode(&token)
+            .ok_or(ParseRequestError::Authorization)?;
+        Ok(Self {
+            token,
+            claims,
+            _mark: PhantomData,
+        })
+    }
+}
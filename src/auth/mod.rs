@@ -3,12 +3,20 @@
 mod api_key;
 mod basic;
 mod bearer;
+#[cfg(feature = "jwt")]
+mod jwt;
+#[cfg(feature = "jwt")]
+mod openid_connect;
 
 use std::collections::HashMap;
 
 pub use api_key::ApiKey;
 pub use basic::Basic;
 pub use bearer::Bearer;
+#[cfg(feature = "jwt")]
+pub use jwt::{Jwt, JwtKey, JwtKeyError, JwtKeys};
+#[cfg(feature = "jwt")]
+pub use openid_connect::{IdToken, OpenIdClaims};
 use poem::Request;
 
 use crate::{registry::MetaParamIn, ParseRequestError};
@@ -21,10 +29,107 @@ pub trait BasicAuthorization: Sized {
 
 /// Represents a bearer authorization extractor.
 pub trait BearerAuthorization: Sized {
+    /// The format of the bearer token, such as `JWT`, used as the
+    /// `bearerFormat` of the security scheme if it is not specified.
+    const BEARER_FORMAT: Option<&'static str> = None;
+
     /// Extract from the HTTP request.
     fn from_request(req: &Request) -> Result<Self, ParseRequestError>;
 }
 
+/// Represents the output of the `checker` function of a security scheme.
+///
+/// `None` rejects the request with `401 Unauthorized`, and an error rejects
+/// the request with its own status code, such as
+/// [`ParseRequestError::Forbidden`].
+pub trait CheckerOutput<T> {
+    /// Converts to the checked value or the error.
+    fn into_result(self) -> Result<T, ParseRequestError>;
+}
+
+impl<T> CheckerOutput<T> for Option<T> {
+    fn into_result(self) -> Result<T, ParseRequestError> {
+        self.ok_or(ParseRequestError::Authorization)
+    }
+}
+
+impl<T> CheckerOutput<T> for Result<T, ParseRequestError> {
+    fn into_result(self) -> Result<T, ParseRequestError> {
+        self
+    }
+}
+
+/// Returns the bearer format of the extractor that is the argument of a
+/// `checker` function.
+#[doc(hidden)]
+pub fn checker_bearer_format<'a, F, A, Fut>(_checker: F) -> Option<&'static str>
+where
+    F: Fn(&'a Request, A) -> Fut,
+    A: BearerAuthorization,
+{
+    A::BEARER_FORMAT
+}
+
+/// Represents a credential that knows the scopes granted to it.
+///
+/// The security schemes with the `check_scopes` attribute use it to verify
+/// the scopes declared by `#[oai(auth("scope1", "scope2"))]`, and the request
+/// is rejected with `403 Forbidden` if any of them is not granted.
+///
+/// # Examples
+///
+/// ```
+/// use poem_openapi::{
+///     auth::{Bearer, ScopeChecker},
+///     OpenApi, SecurityScheme,
+/// };
+///
+/// #[derive(SecurityScheme)]
+/// #[oai(
+///     type = "oauth2",
+///     flows(implicit(
+///         authorization_url = "https://example.com/authorize",
+///         scope(name = "read", desc = "Read data"),
+///         scope(name = "write", desc = "Write data"),
+///     )),
+///     check_scopes
+/// )]
+/// struct MyAuth(Bearer);
+///
+/// impl ScopeChecker for MyAuth {
+///     fn has_scope(&self, scope: &str) -> bool {
+///         // Look up the scopes granted to `self.0.token`.
+///         scope == "read"
+///     }
+/// }
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/data", method = "put")]
+///     async fn write(&self, #[oai(auth("write"))] auth: MyAuth) {}
+/// }
+/// ```
+pub trait ScopeChecker {
+    /// Returns `true` if the scope is granted.
+    fn has_scope(&self, scope: &str) -> bool;
+
+    /// Returns an error with the scopes that are not granted.
+    fn check_scopes(&self, scopes: &[&'static str]) -> Result<(), ParseRequestError> {
+        let missing = scopes
+            .iter()
+            .filter(|scope| !self.has_scope(scope))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(ParseRequestError::InsufficientScope { scopes: missing })
+        }
+    }
+}
+
 /// Represents an api key authorization extractor.
 pub trait ApiKeyAuthorization: Sized {
     /// Extract from the HTTP request.
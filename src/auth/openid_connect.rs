@@ -0,0 +1,137 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use poem::Request;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::{
+    auth::{Bearer, BearerAuthorization, JwtKeys},
+    ParseRequestError,
+};
+
+/// An OpenID Connect ID token, the token is verified with the [`JwtKeys<S>`](crate::auth::JwtKeys)
+/// added to the endpoint.
+///
+/// The ID tokens must have the `iss` and `aud` claims, so the issuer and the
+/// client id must be added to the key set with
+/// [`JwtKeys::issuer`](crate::auth::JwtKeys::issuer) and
+/// [`JwtKeys::audience`](crate::auth::JwtKeys::audience), otherwise a
+/// `500 Internal Server Error` response is returned instead of accepting a
+/// token issued for another client.
+///
+/// # Examples
+///
+/// ```no_run
+/// use poem::{middleware::AddData, EndpointExt, IntoEndpoint};
+/// use poem_openapi::{
+///     auth::{IdToken, JwtKeys},
+///     payload::PlainText,
+///     OpenApi, OpenApiService, SecurityScheme,
+/// };
+///
+/// #[derive(SecurityScheme)]
+/// #[oai(
+///     type = "openid_connect",
+///     openid_connect_url = "https://accounts.example.com/.well-known/openid-configuration"
+/// )]
+/// struct MyAuth(IdToken);
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/me", method = "get")]
+///     async fn me(&self, #[oai(auth)] auth: MyAuth) -> PlainText {
+///         PlainText(auth.0.claims.sub)
+///     }
+/// }
+///
+/// let keys = JwtKeys::new()
+///     .issuer("https://accounts.example.com")
+///     .audience("my-client-id")
+///     .jwks_file("jwks.json")
+///     .unwrap();
+/// let app = OpenApiService::new(Api)
+///     .into_endpoint()
+///     .with(AddData::new(keys));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+pub struct IdToken<S = ()> {
+    /// The raw token.
+    pub token: String,
+    /// The claims of the token.
+    pub claims: OpenIdClaims,
+    _mark: PhantomData<fn() -> S>,
+}
+
+impl<S: 'static> BearerAuthorization for IdToken<S> {
+    const BEARER_FORMAT: Option<&'static str> = Some("JWT");
+
+    fn from_request(req: &Request) -> Result<Self, ParseRequestError> {
+        let Bearer { token } = Bearer::from_request(req)?;
+        let keys = JwtKeys::<S>::from_request(req)?;
+        if !keys.has_issuer_and_audience() {
+            return Err(ParseRequestError::SecuritySchemeNotConfigured(
+                "the issuer and the audience of the ID tokens are not specified.".to_string(),
+            ));
+        }
+        let claims = keys
+            .decode(&token)
+            .ok_or(ParseRequestError::Authorization)?;
+        Ok(Self {
+            token,
+            claims,
+            _mark: PhantomData,
+        })
+    }
+}
+
+/// The claims of an OpenID Connect ID token.
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenIdClaims {
+    /// Issuer identifier.
+    pub iss: String,
+    /// Subject identifier.
+    pub sub: String,
+    /// Audiences that the token is intended for.
+    #[serde(deserialize_with = "deserialize_audience")]
+    pub aud: Vec<String>,
+    /// Expiration time.
+    pub exp: u64,
+    /// Time at which the token was issued.
+    pub iat: u64,
+    /// Time when the authentication occurred.
+    pub auth_time: Option<u64>,
+    /// Value used to associate a client session with the token.
+    pub nonce: Option<String>,
+    /// Authorized party.
+    pub azp: Option<String>,
+    /// Full name.
+    pub name: Option<String>,
+    /// Shorthand name by which the user wishes to be referred to.
+    pub preferred_username: Option<String>,
+    /// Preferred e-mail address.
+    pub email: Option<String>,
+    /// `true` if the e-mail address has been verified.
+    pub email_verified: Option<bool>,
+    /// Other claims.
+    #[serde(flatten)]
+    pub additional: HashMap<String, Value>,
+}
+
+fn deserialize_audience<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Audience {
+        Single(String),
+        Multiple(Vec<String>),
+    }
+
+    Ok(match Audience::deserialize(deserializer)? {
+        Audience::Single(aud) => vec![aud],
+        Audience::Multiple(aud) => aud,
+    })
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use poem::{route::Route, IntoResponse, RequestBody, Result};
 
 use crate::{
-    payload::Payload,
+    payload::{Payload, ResponseContent},
     registry::{MetaApi, MetaMediaType, MetaRequest, MetaResponse, MetaResponses, Registry},
     ParseRequestError,
 };
@@ -59,6 +59,10 @@ pub trait Response: IntoResponse + Sized {
     /// handler.
     const BAD_REQUEST_HANDLER: bool = false;
 
+    /// If true, the media type of the response is selected by the `Accept`
+    /// header of the request, and `406 Not Acceptable` may be returned.
+    const NEGOTIATED: bool = false;
+
     /// Gets metadata of this response.
     fn meta() -> MetaResponses;
 
@@ -70,6 +74,30 @@ pub trait Response: IntoResponse + Sized {
     fn from_parse_request_error(err: ParseRequestError) -> Self {
         unreachable!()
     }
+
+    /// Checks the `Accept` header of the request before the operation is
+    /// invoked, and returns `ParseRequestError::NotAcceptable` if none of the
+    /// negotiated media types is accepted.
+    ///
+    /// The variant returned by the operation is unknown at this point, so a
+    /// response with several negotiated variants passes the check if any of
+    /// them is accepted. The media type of the returned variant is checked
+    /// again by [`Response::into_response_with_request`], which may still
+    /// produce a `406 Not Acceptable` response after the operation is invoked.
+    #[allow(unused_variables)]
+    fn check_accept(request: &poem::Request) -> Result<(), ParseRequestError> {
+        Ok(())
+    }
+
+    /// Convert this response object into an HTTP response to the request,
+    /// such as selecting the media type of the body by the `Accept` header.
+    #[allow(unused_variables)]
+    fn into_response_with_request(
+        self,
+        request: Option<&poem::Request>,
+    ) -> Result<poem::Response, ParseRequestError> {
+        Ok(self.into_response())
+    }
 }
 
 impl Response for () {
@@ -87,24 +115,34 @@ impl Response for () {
     fn register(_registry: &mut Registry) {}
 }
 
-impl<T: Payload + IntoResponse> Response for T {
+impl<T: ResponseContent + IntoResponse> Response for T {
+    const NEGOTIATED: bool = T::NEGOTIATED;
+
     fn meta() -> MetaResponses {
-        MetaResponses {
-            responses: vec![MetaResponse {
-                description: None,
-                status: Some(200),
-                content: vec![MetaMediaType {
-                    content_type: T::CONTENT_TYPE,
-                    schema: T::schema_ref(),
-                }],
-                headers: vec![],
-            }],
-        }
+        let mut responses = vec![MetaResponse {
+            description: None,
+            status: Some(200),
+            content: T::media_types(),
+            headers: T::headers(),
+        }];
+        responses.extend(T::extra_responses());
+        MetaResponses { responses }
     }
 
     fn register(registry: &mut Registry) {
         T::register(registry);
     }
+
+    fn check_accept(request: &poem::Request) -> Result<(), ParseRequestError> {
+        T::check_accept(request)
+    }
+
+    fn into_response_with_request(
+        self,
+        request: Option<&poem::Request>,
+    ) -> Result<poem::Response, ParseRequestError> {
+        ResponseContent::into_response_with_request(self, request)
+    }
 }
 
 /// Represents a OpenAPI tags.
@@ -117,32 +155,77 @@ pub trait Tags {
 }
 
 /// Represents a OpenAPI security scheme.
+#[poem::async_trait]
 pub trait SecurityScheme: Sized {
     /// The name of security scheme.
     const NAME: &'static str;
 
+    /// If true, the scopes required by the operations are checked by
+    /// [`SecurityScheme::check_scopes`] and the operations document a `403`
+    /// response.
+    const CHECK_SCOPES: bool = false;
+
+    /// If true, the credential is optional, so the operations don't document a
+    /// `401` response and allow anonymous access in their security
+    /// requirements.
+    const IS_OPTIONAL: bool = false;
+
     /// Register this security scheme type to registry.
     fn register(registry: &mut Registry);
 
     /// Parse authorization information from request.
-    fn from_request(
+    async fn from_request(
         req: &poem::Request,
         query: &HashMap<String, String>,
     ) -> Result<Self, ParseRequestError>;
+
+    /// Returns the names of the security schemes that can be used as
+    /// alternatives, each of them is documented as a separate security
+    /// requirement.
+    fn security_schemes() -> Vec<&'static str> {
+        vec![Self::NAME]
+    }
+
+    /// Check that the scopes required by the operation are granted to this
+    /// credential.
+    #[allow(unused_variables)]
+    fn check_scopes(&self, scopes: &[&'static str]) -> Result<(), ParseRequestError> {
+        Ok(())
+    }
 }
 
+#[poem::async_trait]
 impl<T: SecurityScheme> SecurityScheme for Option<T> {
     const NAME: &'static str = T::NAME;
+    const CHECK_SCOPES: bool = T::CHECK_SCOPES;
+    const IS_OPTIONAL: bool = true;
 
     fn register(registry: &mut Registry) {
         T::register(registry);
     }
 
-    fn from_request(
+    async fn from_request(
         req: &poem::Request,
         query: &HashMap<String, String>,
     ) -> Result<Self, ParseRequestError> {
-        Ok(T::from_request(req, query).ok())
+        // Only missing or invalid credentials mean anonymous access, the other
+        // errors, such as a forbidden credential, are still returned.
+        match T::from_request(req, query).await {
+            Ok(value) => Ok(Some(value)),
+            Err(ParseRequestError::Authorization) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn security_schemes() -> Vec<&'static str> {
+        T::security_schemes()
+    }
+
+    fn check_scopes(&self, scopes: &[&'static str]) -> Result<(), ParseRequestError> {
+        match self {
+            Some(value) => value.check_scopes(scopes),
+            None => Ok(()),
+        }
     }
 }
 
@@ -0,0 +1,170 @@
+//! Helpers for dumping the OpenAPI document from the command line.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use poem_openapi::{OpenApi, OpenApiService};
+//!
+//! struct Api;
+//!
+//! #[OpenApi]
+//! impl Api {
+//!     #[oai(path = "/hello", method = "get")]
+//!     async fn hello(&self) {}
+//! }
+//!
+//! // `cargo run --bin dump-spec -- --format yaml --output openapi.yaml --check`
+//! fn main() {
+//!     poem_openapi::cli::run(&OpenApiService::new(Api).title("Hello"));
+//! }
+//! ```
+
+use std::{fs, path::PathBuf};
+
+use thiserror::Error;
+
+use crate::{OpenApi, OpenApiService};
+
+/// The format of the dumped document.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SpecFormat {
+    /// JSON
+    Json,
+    /// YAML
+    Yaml,
+}
+
+/// The options of [`run_with_options`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Options {
+    /// The format of the document, defaults to JSON.
+    pub format: SpecFormat,
+
+    /// The file to write the document to, if `None` it is written to stdout.
+    pub output: Option<PathBuf>,
+
+    /// If `true`, the document is compared with the content of `output`
+    /// instead of being written.
+    pub check: bool,
+}
+
+impl Options {
+    /// Parse the options from command line arguments. (excluding the program
+    /// name)
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut options = Options {
+            format: SpecFormat::Json,
+            output: None,
+            check: false,
+        };
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" | "-f" => {
+                    options.format = match args.next().as_deref() {
+                        Some("json") => SpecFormat::Json,
+                        Some("yaml") => SpecFormat::Yaml,
+                        _ => {
+                            return Err(CliError::InvalidArgs(
+                                "`--format` expects `json` or `yaml`".to_string(),
+                            ))
+                        }
+                    };
+                }
+                "--output" | "-o" => match args.next() {
+                    Some(path) => options.output = Some(path.into()),
+                    None => {
+                        return Err(CliError::InvalidArgs(
+                            "`--output` expects a file path".to_string(),
+                        ))
+                    }
+                },
+                "--check" => options.check = true,
+                _ => return Err(CliError::InvalidArgs(format!("unknown argument `{}`", arg))),
+            }
+        }
+
+        if options.check && options.output.is_none() {
+            return Err(CliError::InvalidArgs(
+                "`--check` requires `--output`".to_string(),
+            ));
+        }
+
+        Ok(options)
+    }
+}
+
+/// This type represents errors that occur when dumping the document.
+#[derive(Debug, Error)]
+pub enum CliError {
+    /// Invalid command line arguments.
+    #[error("invalid arguments: {0}")]
+    InvalidArgs(String),
+
+    /// Failed to read or write the document file.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The document file is not up to date.
+    #[error("the file `{}` is out of date", .0.display())]
+    OutOfDate(PathBuf),
+}
+
+/// Dump the document of the service according to the options.
+pub fn run_with_options<T: OpenApi>(
+    service: &OpenApiService<T>,
+    options: &Options,
+) -> Result<(), CliError> {
+    let spec = match options.format {
+        SpecFormat::Json => service.spec(),
+        SpecFormat::Yaml => service.spec_yaml(),
+    };
+
+    match &options.output {
+        Some(path) if options.check => {
+            let current = match fs::read_to_string(path) {
+                Ok(current) => current,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(CliError::OutOfDate(path.clone()))
+                }
+                Err(err) => return Err(err.into()),
+            };
+            // The document redirected from stdout or saved by an editor may
+            // end with a newline.
+            if current.trim_end() != spec.trim_end() {
+                return Err(CliError::OutOfDate(path.clone()));
+            }
+        }
+        Some(path) => fs::write(path, spec)?,
+        None => print!("{}", spec),
+    }
+
+    Ok(())
+}
+
+/// Dump the document of the service according to the command line arguments.
+/// (excluding the program name)
+pub fn run_with_args<T: OpenApi>(
+    service: &OpenApiService<T>,
+    args: impl IntoIterator<Item = String>,
+) -> Result<(), CliError> {
+    run_with_options(service, &Options::parse(args)?)
+}
+
+/// Dump the document of the service according to the arguments of the
+/// current process, and exit with a non-zero status code on failure.
+///
+/// Supported arguments:
+///
+/// | Argument              | description                                        |
+/// |-----------------------|----------------------------------------------------|
+/// | `--format json\|yaml` | The format of the document, defaults to `json`.    |
+/// | `--output <path>`     | Write the document to a file instead of stdout.    |
+/// | `--check`             | Fail if the file of `--output` differs from the document. |
+pub fn run<T: OpenApi>(service: &OpenApiService<T>) {
+    if let Err(err) = run_with_args(service, std::env::args().skip(1)) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
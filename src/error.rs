@@ -1,6 +1,38 @@
-use poem::http::StatusCode;
+use poem::{http::StatusCode, IntoResponse, Response};
+use serde::Serialize;
+use serde_json::Value;
 use thiserror::Error;
 
+use crate::registry::{MetaMediaType, MetaResponse, MetaSchema, MetaSchemaRef, Registry};
+
+/// A single violation found while parsing the request.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Violation {
+    /// A [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) to
+    /// the invalid value, relative to the request body or parameter value.
+    pub pointer: String,
+
+    /// The name of the parameter containing the invalid value, it is only set
+    /// when the violations of several parameters are reported together.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<&'static str>,
+
+    /// The name of the failed validator, such as `maxLength`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validator: Option<&'static str>,
+
+    /// The constraint expected by the failed validator.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+
+    /// The actual input value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<Value>,
+
+    /// A human-readable message.
+    pub message: String,
+}
+
 /// This type represents errors that occur when parsing the HTTP request.
 #[derive(Debug, Error, Clone, Eq, PartialEq)]
 pub enum ParseRequestError {
@@ -12,6 +44,22 @@ pub enum ParseRequestError {
 
         /// The reason for the error.
         reason: String,
+
+        /// All the violations found in the parameter.
+        violations: Vec<Violation>,
+    },
+
+    /// Failed to parse several parameters.
+    #[error("failed to parse params {}: {reason}", .names.iter().map(|name| format!("`{}`", name)).collect::<Vec<_>>().join(", "))]
+    ParseParams {
+        /// The names of the parameters.
+        names: Vec<&'static str>,
+
+        /// The reasons for the errors.
+        reason: String,
+
+        /// All the violations found in the parameters.
+        violations: Vec<Violation>,
     },
 
     /// Failed to parse a request body.
@@ -19,6 +67,9 @@ pub enum ParseRequestError {
     ParseRequestBody {
         /// The reason for the error.
         reason: String,
+
+        /// All the violations found in the request body.
+        violations: Vec<Violation>,
     },
 
     /// The `Content-Type` requested by the client is not supported.
@@ -32,6 +83,20 @@ pub enum ParseRequestError {
     #[error("expect a `Content-Type` header.")]
     ExpectContentType,
 
+    /// None of the media types accepted by the client can be produced.
+    #[error("the media type `{accept}` is not acceptable.")]
+    NotAcceptable {
+        /// The `Accept` header requested by the client.
+        accept: String,
+    },
+
+    /// The request body is larger than the limit.
+    #[error("the request body exceeds the limit of {limit} bytes.")]
+    PayloadTooLarge {
+        /// The maximum size of the request body in bytes.
+        limit: usize,
+    },
+
     /// Poem extractor error.
     #[error("poem extract error: {0}")]
     Extractor(String),
@@ -39,19 +104,236 @@ pub enum ParseRequestError {
     /// Authorization error.
     #[error("authorization error")]
     Authorization,
+
+    /// The credential is not allowed to access the operation.
+    #[error("forbidden")]
+    Forbidden,
+
+    /// The security scheme is not configured correctly, such as the keys used
+    /// to verify the tokens are not added to the endpoint.
+    #[error("the security scheme is not configured: {0}")]
+    SecuritySchemeNotConfigured(String),
+
+    /// The credential is not granted all the scopes required by the operation.
+    #[error("missing scopes: {}", .scopes.join(", "))]
+    InsufficientScope {
+        /// The scopes that are not granted.
+        scopes: Vec<String>,
+    },
+}
+
+impl ParseRequestError {
+    /// Returns the status code of the response for this error.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ParseRequestError::ParseParam { .. } => StatusCode::BAD_REQUEST,
+            ParseRequestError::ParseParams { .. } => StatusCode::BAD_REQUEST,
+            ParseRequestError::ParseRequestBody { .. } => StatusCode::BAD_REQUEST,
+            ParseRequestError::ContentTypeNotSupported { .. } => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseRequestError::ExpectContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseRequestError::NotAcceptable { .. } => StatusCode::NOT_ACCEPTABLE,
+            ParseRequestError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            ParseRequestError::Extractor(_) => StatusCode::BAD_REQUEST,
+            ParseRequestError::Authorization => StatusCode::UNAUTHORIZED,
+            ParseRequestError::Forbidden => StatusCode::FORBIDDEN,
+            ParseRequestError::InsufficientScope { .. } => StatusCode::FORBIDDEN,
+            ParseRequestError::SecuritySchemeNotConfigured(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Returns all the violations of this error.
+    pub fn violations(&self) -> &[Violation] {
+        match self {
+            ParseRequestError::ParseParam { violations, .. } => violations,
+            ParseRequestError::ParseParams { violations, .. } => violations,
+            ParseRequestError::ParseRequestBody { violations, .. } => violations,
+            _ => &[],
+        }
+    }
+
+    /// Merges the errors of the parameters of an operation into one error,
+    /// the violations of each parameter are marked with its name.
+    #[doc(hidden)]
+    pub fn merge_params(mut errors: Vec<ParseRequestError>) -> ParseRequestError {
+        if errors.len() == 1 {
+            return errors.remove(0);
+        }
+
+        let mut names = Vec::new();
+        let mut reasons = Vec::new();
+        let mut all_violations = Vec::new();
+        for err in errors {
+            match err {
+                ParseRequestError::ParseParam {
+                    name,
+                    reason,
+                    violations,
+                } => {
+                    names.push(name);
+                    if violations.is_empty() {
+                        all_violations.push(Violation {
+                            pointer: String::new(),
+                            parameter: Some(name),
+                            validator: None,
+                            expected: None,
+                            actual: None,
+                            message: reason.clone(),
+                        });
+                    }
+                    all_violations.extend(violations.into_iter().map(|violation| Violation {
+                        parameter: Some(name),
+                        ..violation
+                    }));
+                    reasons.push(format!("`{}`: {}", name, reason));
+                }
+                err => {
+                    all_violations.extend_from_slice(err.violations());
+                    reasons.push(err.to_string());
+                }
+            }
+        }
+
+        ParseRequestError::ParseParams {
+            names,
+            reason: reasons.join("; "),
+            violations: all_violations,
+        }
+    }
+
+    /// Registers the `Error` schema of the `application/problem+json`
+    /// responses.
+    #[doc(hidden)]
+    pub fn register(registry: &mut Registry) {
+        registry.create_schema("Error", |_| {
+            let string = || MetaSchemaRef::Inline(MetaSchema::new("string"));
+            let violation = MetaSchema {
+                required: vec!["pointer", "message"],
+                properties: vec![
+                    ("pointer", string()),
+                    ("parameter", string()),
+                    ("validator", string()),
+                    ("expected", MetaSchemaRef::Inline(MetaSchema::ANY)),
+                    ("actual", MetaSchemaRef::Inline(MetaSchema::ANY)),
+                    ("message", string()),
+                ],
+                ..MetaSchema::new("object")
+            };
+
+            MetaSchema {
+                description: Some("An RFC 7807 problem details object."),
+                required: vec!["type", "title", "status", "detail"],
+                properties: vec![
+                    ("type", string()),
+                    ("title", string()),
+                    (
+                        "status",
+                        MetaSchemaRef::Inline(MetaSchema {
+                            format: Some("int32"),
+                            ..MetaSchema::new("integer")
+                        }),
+                    ),
+                    ("detail", string()),
+                    ("parameter", string()),
+                    (
+                        "parameters",
+                        MetaSchemaRef::Inline(MetaSchema {
+                            items: Some(Box::new(string())),
+                            ..MetaSchema::new("array")
+                        }),
+                    ),
+                    (
+                        "violations",
+                        MetaSchemaRef::Inline(MetaSchema {
+                            items: Some(Box::new(MetaSchemaRef::Inline(violation))),
+                            ..MetaSchema::new("array")
+                        }),
+                    ),
+                ],
+                ..MetaSchema::new("object")
+            }
+        });
+    }
+
+    /// Returns the meta of the error response with the specified status code.
+    #[doc(hidden)]
+    pub fn meta_response(status: u16) -> MetaResponse {
+        MetaResponse {
+            description: StatusCode::from_u16(status)
+                .ok()
+                .and_then(|status| status.canonical_reason()),
+            status: Some(status),
+            content: vec![MetaMediaType {
+                content_type: "application/problem+json",
+                schema: MetaSchemaRef::Reference("Error"),
+            }],
+            headers: Vec::new(),
+        }
+    }
 }
 
 impl From<ParseRequestError> for poem::Error {
     fn from(err: ParseRequestError) -> Self {
         match &err {
-            ParseRequestError::ParseParam { .. } => poem::Error::bad_request(err),
-            ParseRequestError::ParseRequestBody { .. } => poem::Error::bad_request(err),
-            ParseRequestError::ContentTypeNotSupported { .. } => {
-                poem::Error::method_not_allowed(err)
-            }
-            ParseRequestError::ExpectContentType => poem::Error::method_not_allowed(err),
-            ParseRequestError::Extractor(_) => poem::Error::bad_request(err),
             ParseRequestError::Authorization => poem::Error::new(StatusCode::UNAUTHORIZED),
+            _ => poem::Error::new(err.status()).with_reason(err),
+        }
+    }
+}
+
+/// Creates an [RFC 7807](https://datatracker.ietf.org/doc/html/rfc7807)
+/// `application/problem+json` response.
+///
+/// The error is also stored in the extensions of the response, so that it can
+/// be rendered differently by [`OpenApiService::error_handler`](crate::OpenApiService::error_handler).
+impl IntoResponse for ParseRequestError {
+    fn into_response(self) -> Response {
+        let mut members = serde_json::Map::new();
+        if let ParseRequestError::ParseParam { name, .. } = &self {
+            members.insert("parameter".to_string(), Value::String(name.to_string()));
+        }
+        if let ParseRequestError::ParseParams { names, .. } = &self {
+            members.insert(
+                "parameters".to_string(),
+                Value::Array(
+                    names
+                        .iter()
+                        .map(|name| Value::String(name.to_string()))
+                        .collect(),
+                ),
+            );
         }
+        if !self.violations().is_empty() {
+            members.insert(
+                "violations".to_string(),
+                serde_json::to_value(self.violations()).unwrap_or_default(),
+            );
+        }
+
+        let mut resp = problem_response(self.status(), self.to_string(), members);
+        resp.extensions_mut().insert(self);
+        resp
     }
 }
+
+/// Creates an `application/problem+json` response with the standard members of
+/// a problem and the extension `members`.
+pub(crate) fn problem_response(
+    status: StatusCode,
+    detail: String,
+    members: serde_json::Map<String, Value>,
+) -> Response {
+    let mut problem = serde_json::Map::new();
+    problem.insert("type".to_string(), Value::String("about:blank".to_string()));
+    problem.insert(
+        "title".to_string(),
+        Value::String(status.canonical_reason().unwrap_or_default().to_string()),
+    );
+    problem.insert("status".to_string(), Value::from(status.as_u16()));
+    problem.insert("detail".to_string(), Value::String(detail));
+    problem.extend(members);
+
+    Response::builder()
+        .status(status)
+        .content_type("application/problem+json")
+        .body(Value::Object(problem).to_string())
+}
@@ -7,6 +7,7 @@
 
 pub mod auth;
 mod base;
+pub mod cli;
 mod error;
 mod openapi;
 #[doc(hidden)]
@@ -17,12 +18,13 @@ pub mod registry;
 pub mod types;
 #[doc(hidden)]
 pub mod ui;
+mod utils;
 #[doc(hidden)]
 pub mod validation;
 
 pub use base::{CombinedAPI, OpenApi, Request, Response, SecurityScheme, Tags};
-pub use error::ParseRequestError;
-pub use openapi::OpenApiService;
+pub use error::{ParseRequestError, Violation};
+pub use openapi::{OpenApiService, OpenApiVersion};
 #[doc(hidden)]
 pub use poem;
 #[doc = include_str!("docs/enum.md")]
@@ -37,9 +39,12 @@ pub use poem_openapi_derive::OpenApi;
 pub use poem_openapi_derive::Request;
 #[doc = include_str!("docs/response.md")]
 pub use poem_openapi_derive::Response;
+#[doc = include_str!("docs/security_scheme.md")]
 pub use poem_openapi_derive::SecurityScheme;
 #[doc = include_str!("docs/tags.md")]
 pub use poem_openapi_derive::Tags;
+#[doc = include_str!("docs/union.md")]
+pub use poem_openapi_derive::Union;
 #[doc(hidden)]
 pub use serde;
 #[doc(hidden)]
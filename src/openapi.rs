@@ -1,17 +1,46 @@
-use poem::{route, route::Route, IntoEndpoint};
+use std::sync::Arc;
+
+use poem::{endpoint::make_sync, route, route::Route, EndpointExt, IntoEndpoint, Response};
 
 use crate::{
     poem::Endpoint,
-    registry::{Document, MetaInfo, MetaServer, Registry},
+    registry::{Document, MetaApi, MetaInfo, MetaServer, Registry},
     ui::create_ui_endpoint,
-    OpenApi,
+    OpenApi, ParseRequestError,
 };
 
+/// The version of the OpenAPI Specification used by the generated document.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OpenApiVersion {
+    /// OpenAPI 3.0.0
+    V3_0_0,
+    /// OpenAPI 3.1.0
+    ///
+    /// Reference: <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md>
+    V3_1_0,
+}
+
+impl OpenApiVersion {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            OpenApiVersion::V3_0_0 => "3.0.0",
+            OpenApiVersion::V3_1_0 => "3.1.0",
+        }
+    }
+}
+
+type Webhooks = (fn() -> Vec<MetaApi>, fn(&mut Registry));
+
+type ErrorHandler = Arc<dyn Fn(ParseRequestError) -> Response + Send + Sync>;
+
 /// An OpenAPI service for Poem.
 pub struct OpenApiService<T> {
     api: T,
+    version: OpenApiVersion,
     info: Option<MetaInfo>,
     servers: Vec<MetaServer>,
+    webhooks: Vec<Webhooks>,
+    error_handler: Option<ErrorHandler>,
 }
 
 impl<T> OpenApiService<T> {
@@ -20,11 +49,22 @@ impl<T> OpenApiService<T> {
     pub fn new(api: T) -> Self {
         Self {
             api,
+            version: OpenApiVersion::V3_0_0,
             info: None,
             servers: Vec::new(),
+            webhooks: Vec::new(),
+            error_handler: None,
         }
     }
 
+    /// Sets the version of the OpenAPI Specification used by the document,
+    /// defaults to `3.0.0`.
+    #[must_use]
+    pub fn openapi_version(mut self, version: OpenApiVersion) -> Self {
+        self.version = version;
+        self
+    }
+
     /// Sets the title of the API container.
     ///
     /// Reference: <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#infoObject>
@@ -75,25 +115,113 @@ impl<T> OpenApiService<T> {
         self
     }
 
-    /// Create the Swagger UI endpoint.
+    /// Appends the webhooks defined by an API object to the document.
+    ///
+    /// The path of each operation without the leading `/` is used as the name
+    /// of the webhook. Webhooks are only supported by OpenAPI 3.1, and are
+    /// omitted when generating a 3.0 document.
+    ///
+    /// Reference: <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#fixed-fields>
     #[must_use]
-    pub fn swagger_ui(&self, absolute_uri: impl AsRef<str>) -> impl Endpoint
+    pub fn webhooks<W: OpenApi>(mut self) -> Self {
+        self.webhooks.push((W::meta, W::register));
+        self
+    }
+
+    /// Sets a handler to convert the errors that occur when parsing the
+    /// requests into responses, for all operations of the API.
+    ///
+    /// By default, these errors are rendered as [RFC 7807](https://datatracker.ietf.org/doc/html/rfc7807)
+    /// `application/problem+json` responses. The handler is not called for
+    /// operations whose response type has a `bad_request_handler`.
+    #[must_use]
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(ParseRequestError) -> Response + Send + Sync + 'static,
+    {
+        self.error_handler = Some(Arc::new(f));
+        self
+    }
+
+    fn document<R>(&self, f: impl FnOnce(&Document<'_>) -> R) -> R
     where
         T: OpenApi,
     {
         let mut registry = Registry::new();
         let metadata = T::meta();
+        let mut webhooks = Vec::new();
 
         T::register(&mut registry);
+        for (meta, register) in &self.webhooks {
+            webhooks.extend(meta());
+            register(&mut registry);
+        }
 
         let doc = Document {
+            version: self.version,
             info: self.info.as_ref(),
             servers: &self.servers,
             apis: &metadata,
+            webhooks: &webhooks,
             registry: &registry,
         };
-        let doc_json = serde_json::to_string_pretty(&doc).unwrap();
-        create_ui_endpoint(absolute_uri.as_ref(), &doc_json)
+        f(&doc)
+    }
+
+    /// Returns the OpenAPI document as a JSON string.
+    #[must_use]
+    pub fn spec(&self) -> String
+    where
+        T: OpenApi,
+    {
+        self.document(|doc| serde_json::to_string_pretty(doc).unwrap())
+    }
+
+    /// Returns the OpenAPI document as a YAML string.
+    #[must_use]
+    pub fn spec_yaml(&self) -> String
+    where
+        T: OpenApi,
+    {
+        self.document(|doc| serde_yaml::to_string(doc).unwrap())
+    }
+
+    /// Create an endpoint that serves the OpenAPI document at `/openapi.json`
+    /// and `/openapi.yaml`.
+    #[must_use]
+    pub fn spec_endpoint(&self) -> impl Endpoint
+    where
+        T: OpenApi,
+    {
+        let spec_json = self.spec();
+        let spec_yaml = self.spec_yaml();
+
+        route()
+            .at(
+                "/openapi.json",
+                make_sync(move |_| {
+                    Response::builder()
+                        .content_type("application/json")
+                        .body(spec_json.clone())
+                }),
+            )
+            .at(
+                "/openapi.yaml",
+                make_sync(move |_| {
+                    Response::builder()
+                        .content_type("application/yaml")
+                        .body(spec_yaml.clone())
+                }),
+            )
+    }
+
+    /// Create the Swagger UI endpoint.
+    #[must_use]
+    pub fn swagger_ui(&self, absolute_uri: impl AsRef<str>) -> impl Endpoint
+    where
+        T: OpenApi,
+    {
+        create_ui_endpoint(absolute_uri.as_ref(), &self.spec())
     }
 }
 
@@ -101,6 +229,21 @@ impl<T: OpenApi> IntoEndpoint for OpenApiService<T> {
     type Endpoint = Route;
 
     fn into_endpoint(self) -> Self::Endpoint {
-        self.api.add_routes(route())
+        let api_route = self.api.add_routes(route());
+        match self.error_handler {
+            Some(error_handler) => route().nest_no_strip(
+                "/",
+                api_route.after(move |mut resp: Response| {
+                    let error_handler = error_handler.clone();
+                    async move {
+                        match resp.extensions_mut().remove::<ParseRequestError>() {
+                            Some(err) => error_handler(err),
+                            None => resp,
+                        }
+                    }
+                }),
+            ),
+            None => api_route,
+        }
     }
 }
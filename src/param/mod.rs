@@ -1,6 +1,13 @@
 use std::{borrow::Cow, collections::HashMap};
 
-use crate::{poem::Request, registry::MetaParamIn};
+use crate::{
+    payload::schema::{properties, registry, resolve},
+    poem::Request,
+    registry::{MetaParamIn, MetaParamStyle, Registry},
+    types::{Type, TypeName},
+    utils::TypeCache,
+    SecurityScheme,
+};
 
 pub fn get<'a>(
     name: &str,
@@ -24,3 +31,94 @@ pub fn get<'a>(
             .map(Cow::Owned),
     }
 }
+
+/// Returns all the values of a query parameter according to the `style` and
+/// `explode` of this parameter.
+///
+/// Only the values of arrays and objects are split, the properties of an
+/// object are returned as a flat list of keys and values. The properties of an
+/// exploded `form` object are the query parameters named after its declared
+/// properties, or all the query parameters except `other_params` for a map.
+pub fn get_query_values<'a, T: Type + 'static>(
+    name: &str,
+    style: Option<MetaParamStyle>,
+    explode: bool,
+    other_params: &[&str],
+    query: &'a [(String, String)],
+) -> Vec<&'a str> {
+    let is_object = matches!(T::NAME, TypeName::Normal { ty: "object", .. });
+    let is_array = matches!(T::NAME, TypeName::Array(_));
+
+    match style {
+        Some(MetaParamStyle::DeepObject) => {
+            let mut values = Vec::new();
+            for (key, value) in query {
+                let key = key
+                    .strip_prefix(name)
+                    .and_then(|key| key.strip_prefix('['))
+                    .and_then(|key| key.strip_suffix(']'));
+                if let Some(key) = key {
+                    values.push(key);
+                    values.push(value.as_str());
+                }
+            }
+            values
+        }
+        _ if is_object && explode => {
+            let registry = registry::<T>();
+            let schema_ref = T::schema_ref();
+            let mut declared = Vec::new();
+            if let Some(schema) = resolve(registry, &schema_ref) {
+                properties(registry, schema, &mut declared);
+            }
+
+            let mut values = Vec::new();
+            for (key, value) in query {
+                let is_property = if declared.is_empty() {
+                    !other_params.contains(&key.as_str())
+                } else {
+                    declared.iter().any(|(name, _)| name == key)
+                };
+                if is_property {
+                    values.push(key.as_str());
+                    values.push(value.as_str());
+                }
+            }
+            values
+        }
+        _ if (is_array || is_object) && !explode => {
+            let delimiter = match style {
+                Some(MetaParamStyle::SpaceDelimited) => ' ',
+                Some(MetaParamStyle::PipeDelimited) => '|',
+                _ => ',',
+            };
+            query
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.split(delimiter).collect())
+                .unwrap_or_default()
+        }
+        _ => query
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .collect(),
+    }
+}
+
+/// Returns the names of the query parameters used by the security scheme `T`,
+/// they are never the properties of an exploded `form` object.
+pub fn security_query_params<T: SecurityScheme + 'static>() -> &'static [&'static str] {
+    static PARAMS: TypeCache<[&'static str]> = TypeCache::new();
+
+    PARAMS.get_or_init::<T>(|| {
+        let mut registry = Registry::new();
+        T::register(&mut registry);
+        registry
+            .security_schemes
+            .values()
+            .filter(|scheme| scheme.key_in == Some("query"))
+            .filter_map(|scheme| scheme.name)
+            .collect()
+    })
+}
@@ -0,0 +1,406 @@
+use std::{
+    io::{Error as IoError, SeekFrom},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures_util::{stream, TryStreamExt};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use poem::{
+    http::{header, StatusCode},
+    Body, IntoResponse, Request, Response,
+};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+use tokio_util::io::{ReaderStream, StreamReader};
+
+use crate::{
+    payload::ResponseContent,
+    registry::{MetaHeader, MetaMediaType, MetaResponse, MetaSchema, MetaSchemaRef, Registry},
+    ParseRequestError,
+};
+
+/// The characters that are percent-encoded in the `filename*` parameter.
+const FILENAME_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'%')
+    .add(b'\'')
+    .add(b';')
+    .add(b'\\');
+
+/// How the file is presented by the user agent.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AttachmentType {
+    /// The file is displayed inside the web page, or as the web page.
+    Inline,
+    /// The file is downloaded and saved locally.
+    Attachment,
+}
+
+/// A file download payload, the file is streamed rather than read into
+/// memory.
+///
+/// The response has the `Content-Disposition`, `Content-Length`, `ETag` and
+/// `Last-Modified` headers. Conditional requests with `If-None-Match` are
+/// answered with `304 Not Modified`, and a single byte range requested by the
+/// `Range` header is answered with `206 Partial Content`, or `416 Range Not
+/// Satisfiable` if it is outside the file.
+///
+/// # Examples
+///
+/// ```
+/// use poem_openapi::{payload::Attachment, OpenApi, Response};
+///
+/// #[derive(Response)]
+/// enum ReportResponse {
+///     #[oai(status = 200)]
+///     Ok(Attachment),
+///     #[oai(status = 404)]
+///     NotFound,
+/// }
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/report", method = "get")]
+///     async fn report(&self) -> ReportResponse {
+///         match Attachment::open("report.pdf").await {
+///             Ok(attachment) => ReportResponse::Ok(attachment),
+///             Err(_) => ReportResponse::NotFound,
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Attachment {
+    file: File,
+    len: u64,
+    modified: Option<SystemTime>,
+    filename: Option<String>,
+    attachment_type: AttachmentType,
+    content_type: Option<String>,
+}
+
+impl Attachment {
+    /// Open the file at `path`, the file name of the path is used as the
+    /// file name of the attachment.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, IoError> {
+        let path = path.as_ref();
+        let attachment = Self::new(File::open(path).await?).await?;
+        Ok(match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) => attachment.filename(name),
+            None => attachment,
+        })
+    }
+
+    /// Create an attachment from an opened file.
+    pub async fn new(file: File) -> Result<Self, IoError> {
+        let metadata = file.metadata().await?;
+        Ok(Self {
+            file,
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            filename: None,
+            attachment_type: AttachmentType::Attachment,
+            content_type: None,
+        })
+    }
+
+    /// Set the file name in the `Content-Disposition` header.
+    #[must_use]
+    pub fn filename(self, filename: impl Into<String>) -> Self {
+        Self {
+            filename: Some(filename.into()),
+            ..self
+        }
+    }
+
+    /// Set the disposition type, defaults to [`AttachmentType::Attachment`].
+    #[must_use]
+    pub fn attachment_type(self, attachment_type: AttachmentType) -> Self {
+        Self {
+            attachment_type,
+            ..self
+        }
+    }
+
+    /// Set the content type, defaults to `application/octet-stream`.
+    #[must_use]
+    pub fn content_type(self, content_type: impl Into<String>) -> Self {
+        Self {
+            content_type: Some(content_type.into()),
+            ..self
+        }
+    }
+
+    fn etag(&self) -> String {
+        let modified = self
+            .modified
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        format!("\"{:x}-{:x}\"", self.len, modified)
+    }
+
+    fn content_disposition(&self) -> String {
+        let mut value = match self.attachment_type {
+            AttachmentType::Inline => "inline".to_string(),
+            AttachmentType::Attachment => "attachment".to_string(),
+        };
+        if let Some(filename) = &self.filename {
+            if filename.is_ascii() {
+                value.push_str(&format!(
+                    "; filename=\"{}\"",
+                    filename.replace('\\', "\\\\").replace('"', "\\\"")
+                ));
+            } else {
+                value.push_str(&format!(
+                    "; filename*=UTF-8''{}",
+                    utf8_percent_encode(filename, FILENAME_ENCODE_SET)
+                ));
+            }
+        }
+        value
+    }
+
+    /// Returns `true` if the `If-None-Match` or `If-Modified-Since` header
+    /// matches the current version of the file.
+    fn not_modified(&self, request: &Request, etag: &str) -> bool {
+        if let Some(if_none_match) = header_str(request, header::IF_NONE_MATCH) {
+            return if_none_match
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag);
+        }
+        match (
+            header_str(request, header::IF_MODIFIED_SINCE)
+                .and_then(|value| httpdate::parse_http_date(value).ok()),
+            self.modified,
+        ) {
+            (Some(since), Some(modified)) => truncate_to_secs(modified) <= since,
+            _ => false,
+        }
+    }
+
+    /// Returns the byte range requested by the `Range` header, the range is
+    /// ignored if `If-Range` does not match the current version of the file.
+    fn range(&self, request: &Request, etag: &str) -> Option<Result<(u64, u64), ()>> {
+        let range = header_str(request, header::RANGE)?;
+        if let Some(if_range) = header_str(request, header::IF_RANGE) {
+            let matched = match httpdate::parse_http_date(if_range) {
+                Ok(date) => self.modified.map(truncate_to_secs) == Some(date),
+                Err(_) => if_range == etag,
+            };
+            if !matched {
+                return None;
+            }
+        }
+        parse_range(range, self.len)
+    }
+}
+
+fn header_str(request: &Request, name: header::HeaderName) -> Option<&str> {
+    request
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+fn truncate_to_secs(time: SystemTime) -> SystemTime {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => UNIX_EPOCH + std::time::Duration::from_secs(duration.as_secs()),
+        Err(_) => time,
+    }
+}
+
+/// Parses a `Range` header with a single byte range, returns `None` if the
+/// header is not supported and should be ignored, or `Err` if the range is
+/// not satisfiable.
+fn parse_range(range: &str, len: u64) -> Option<Result<(u64, u64), ()>> {
+    let range = range.trim().strip_prefix("bytes=")?;
+    if range.contains(',') {
+        return None;
+    }
+    let (start, end) = range.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+
+    let (start, end) = if start.is_empty() {
+        let suffix = end.parse::<u64>().ok()?;
+        if suffix == 0 {
+            return Some(Err(()));
+        }
+        (len.saturating_sub(suffix), len.checked_sub(1)?)
+    } else {
+        let start = start.parse::<u64>().ok()?;
+        let end = match end {
+            "" => len.saturating_sub(1),
+            end => end.parse::<u64>().ok()?.min(len.saturating_sub(1)),
+        };
+        (start, end)
+    };
+
+    if start >= len || start > end {
+        Some(Err(()))
+    } else {
+        Some(Ok((start, end)))
+    }
+}
+
+/// Returns a body that reads `len` bytes of the file from `start`, the file is
+/// seeked when the body is first read.
+fn file_body(mut file: File, start: u64, len: u64) -> Body {
+    let chunks = stream::once(async move {
+        file.seek(SeekFrom::Start(start)).await?;
+        Ok::<_, IoError>(ReaderStream::new(file.take(len)))
+    })
+    .try_flatten();
+    Body::from_async_read(StreamReader::new(chunks))
+}
+
+fn binary_schema() -> MetaSchemaRef {
+    MetaSchemaRef::Inline(MetaSchema {
+        format: Some("binary"),
+        ..MetaSchema::new("string")
+    })
+}
+
+fn meta_header(name: &'static str, description: &'static str, ty: &'static str) -> MetaHeader {
+    MetaHeader {
+        name,
+        description: Some(description),
+        required: false,
+        schema: MetaSchemaRef::Inline(MetaSchema::new(ty)),
+    }
+}
+
+impl IntoResponse for Attachment {
+    fn into_response(self) -> Response {
+        match self.into_response_with_request(None) {
+            Ok(resp) => resp,
+            Err(err) => err.into_response(),
+        }
+    }
+}
+
+impl ResponseContent for Attachment {
+    fn media_types() -> Vec<MetaMediaType> {
+        vec![MetaMediaType {
+            content_type: "application/octet-stream",
+            schema: binary_schema(),
+        }]
+    }
+
+    fn register(_registry: &mut Registry) {}
+
+    fn headers() -> Vec<MetaHeader> {
+        vec![
+            meta_header(
+                "CONTENT-DISPOSITION",
+                "Whether the file is displayed inline or downloaded, and its file name.",
+                "string",
+            ),
+            meta_header(
+                "CONTENT-LENGTH",
+                "The size of the body in bytes.",
+                "integer",
+            ),
+            meta_header("ETAG", "The version of the file.", "string"),
+            meta_header(
+                "LAST-MODIFIED",
+                "The time when the file was last modified.",
+                "string",
+            ),
+            meta_header(
+                "ACCEPT-RANGES",
+                "The file supports byte range requests.",
+                "string",
+            ),
+        ]
+    }
+
+    fn extra_responses() -> Vec<MetaResponse> {
+        let mut partial_headers = Self::headers();
+        partial_headers.push(meta_header(
+            "CONTENT-RANGE",
+            "The range of the file contained in the body.",
+            "string",
+        ));
+        vec![
+            MetaResponse {
+                description: Some("Partial Content"),
+                status: Some(206),
+                content: Self::media_types(),
+                headers: partial_headers,
+            },
+            MetaResponse {
+                description: Some("Not Modified"),
+                status: Some(304),
+                content: vec![],
+                headers: vec![meta_header("ETAG", "The version of the file.", "string")],
+            },
+            MetaResponse {
+                description: Some("Range Not Satisfiable"),
+                status: Some(416),
+                content: vec![],
+                headers: vec![meta_header(
+                    "CONTENT-RANGE",
+                    "The size of the file, such as `bytes */1024`.",
+                    "string",
+                )],
+            },
+        ]
+    }
+
+    fn into_response_with_request(
+        self,
+        request: Option<&Request>,
+    ) -> Result<Response, ParseRequestError> {
+        let etag = self.etag();
+        let mut builder = Response::builder()
+            .header(header::ETAG, etag.as_str())
+            .header(header::ACCEPT_RANGES, "bytes");
+        if let Some(modified) = self.modified {
+            builder = builder.header(header::LAST_MODIFIED, httpdate::fmt_http_date(modified));
+        }
+
+        if let Some(request) = request {
+            if self.not_modified(request, &etag) {
+                return Ok(builder.status(StatusCode::NOT_MODIFIED).finish());
+            }
+        }
+
+        let range = request.and_then(|request| self.range(request, &etag));
+        let (start, end) = match range {
+            Some(Ok(range)) => range,
+            Some(Err(())) => {
+                return Ok(builder
+                    .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                    .header(header::CONTENT_RANGE, format!("bytes */{}", self.len))
+                    .finish());
+            }
+            None => (0, self.len.saturating_sub(1)),
+        };
+        let len = if self.len == 0 { 0 } else { end - start + 1 };
+
+        builder = builder
+            .header(header::CONTENT_DISPOSITION, self.content_disposition())
+            .header(header::CONTENT_LENGTH, len)
+            .content_type(
+                self.content_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream"),
+            );
+        if range.is_some() {
+            builder = builder.status(StatusCode::PARTIAL_CONTENT).header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, self.len),
+            );
+        }
+
+        Ok(builder.body(file_body(self.file, start, len)))
+    }
+}
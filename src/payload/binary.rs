@@ -34,6 +34,7 @@ impl Payload for Binary {
         Ok(Self(<Vec<u8>>::from_request(request, body).await.map_err(
             |err| ParseRequestError::ParseRequestBody {
                 reason: err.to_string(),
+                violations: Vec::new(),
             },
         )?))
     }
@@ -0,0 +1,99 @@
+use bytes::Bytes;
+use futures_util::Stream;
+use poem::{Body, Request, RequestBody};
+use tokio::io::{AsyncRead, AsyncReadExt, Error as IoError};
+use tokio_util::io::ReaderStream;
+
+use crate::{
+    payload::Payload,
+    registry::{MetaSchema, MetaSchemaRef},
+    ParseRequestError,
+};
+
+/// A binary payload that is not buffered, the body is read as it arrives.
+///
+/// Use it instead of [`Binary`](super::Binary) for large uploads, and set the
+/// `body_limit` attribute of the operation to limit the size of the body. If
+/// the body exceeds the limit, reading it fails and `413 Payload Too Large` is
+/// returned instead of the response of the operation.
+///
+/// # Examples
+///
+/// ```
+/// use poem_openapi::{
+///     payload::{BinaryStream, PlainText},
+///     OpenApi,
+/// };
+/// use tokio::io::AsyncReadExt;
+///
+/// async fn count_bytes(data: BinaryStream) -> std::io::Result<usize> {
+///     let mut reader = data.into_async_read();
+///     let mut buf = [0; 4096];
+///     let mut size = 0;
+///     loop {
+///         match reader.read(&mut buf).await? {
+///             0 => return Ok(size),
+///             n => size += n,
+///         }
+///     }
+/// }
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/upload", method = "put", body_limit = 1073741824)]
+///     async fn upload(&self, data: BinaryStream) -> PlainText {
+///         match count_bytes(data).await {
+///             Ok(size) => PlainText(size.to_string()),
+///             Err(err) => PlainText(err.to_string()),
+///         }
+///     }
+/// }
+/// ```
+pub struct BinaryStream(Body);
+
+impl BinaryStream {
+    /// Consumes this payload to return a [`Vec<u8>`] that contains all data.
+    pub async fn into_vec(self) -> Result<Vec<u8>, IoError> {
+        let mut data = Vec::new();
+        self.into_async_read().read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Consumes this payload to return a reader.
+    pub fn into_async_read(self) -> impl AsyncRead + Unpin + Send + 'static {
+        self.0.into_async_read()
+    }
+
+    /// Consumes this payload to return a stream of the chunks of the body.
+    pub fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<Bytes, IoError>> + Unpin + Send + 'static {
+        ReaderStream::new(self.into_async_read())
+    }
+}
+
+#[poem::async_trait]
+impl Payload for BinaryStream {
+    const CONTENT_TYPE: &'static str = "application/octet-stream";
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(MetaSchema {
+            format: Some("binary"),
+            ..MetaSchema::new("string")
+        })
+    }
+
+    async fn from_request(
+        _request: &Request,
+        body: &mut RequestBody,
+    ) -> Result<Self, ParseRequestError> {
+        Ok(Self(body.take().map_err(|err| {
+            ParseRequestError::ParseRequestBody {
+                reason: err.to_string(),
+                violations: Vec::new(),
+            }
+        })?))
+    }
+}
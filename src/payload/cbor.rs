@@ -0,0 +1,66 @@
+use poem::{IntoResponse, Request, RequestBody, Response};
+use serde_json::Value;
+
+use crate::{
+    payload::{
+        codec::{self, Codec},
+        Payload,
+    },
+    registry::{MetaSchemaRef, Registry},
+    types::{ParseFromJSON, ToJSON},
+    ParseRequestError,
+};
+
+/// A CBOR payload.
+///
+/// It has the same schema as [`Json`](super::Json), so every type that can be
+/// used in a JSON payload can be used in it.
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cbor<T>(pub T);
+
+impl<T> From<T> for Cbor<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Codec for Cbor<T> {
+    const CONTENT_TYPE: &'static str = "application/cbor";
+
+    fn decode(data: &[u8]) -> Result<Value, String> {
+        serde_cbor::from_slice(data).map_err(|err| err.to_string())
+    }
+
+    fn encode(value: &Value) -> Result<Vec<u8>, String> {
+        serde_cbor::to_vec(value).map_err(|err| err.to_string())
+    }
+}
+
+#[poem::async_trait]
+impl<T: ParseFromJSON> Payload for Cbor<T> {
+    const CONTENT_TYPE: &'static str = <Self as Codec>::CONTENT_TYPE;
+
+    fn schema_ref() -> MetaSchemaRef {
+        T::schema_ref()
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
+    async fn from_request(
+        request: &Request,
+        body: &mut RequestBody,
+    ) -> Result<Self, ParseRequestError> {
+        codec::from_request::<Self, T>(request, body)
+            .await
+            .map(Self)
+    }
+}
+
+impl<T: ToJSON> IntoResponse for Cbor<T> {
+    fn into_response(self) -> Response {
+        codec::into_response::<Self, T>(&self.0)
+    }
+}
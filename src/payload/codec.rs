@@ -0,0 +1,61 @@
+//! The implementation shared by the binary payloads that have the same schema
+//! as [`Json`](super::Json), such as [`MsgPack`](super::MsgPack) and
+//! [`Cbor`](super::Cbor).
+
+use poem::{http::StatusCode, FromRequest, Request, RequestBody, Response};
+use serde_json::Value;
+
+use crate::{
+    error::problem_response,
+    payload::schema::check_content_type,
+    types::{ParseFromJSON, ToJSON},
+    ParseRequestError,
+};
+
+/// A binary encoding of JSON values.
+pub(crate) trait Codec {
+    /// The content type of the encoded data.
+    const CONTENT_TYPE: &'static str;
+
+    fn decode(data: &[u8]) -> Result<Value, String>;
+
+    fn encode(value: &Value) -> Result<Vec<u8>, String>;
+}
+
+pub(crate) async fn from_request<C: Codec, T: ParseFromJSON>(
+    request: &Request,
+    body: &mut RequestBody,
+) -> Result<T, ParseRequestError> {
+    check_content_type(request, C::CONTENT_TYPE)?;
+
+    let data = <Vec<u8>>::from_request(request, body)
+        .await
+        .map_err(|err| ParseRequestError::ParseRequestBody {
+            reason: err.to_string(),
+            violations: Vec::new(),
+        })?;
+    let value = C::decode(&data).map_err(|reason| ParseRequestError::ParseRequestBody {
+        reason,
+        violations: Vec::new(),
+    })?;
+    T::parse_from_json(value).map_err(|err| {
+        let violations = err.violations().to_vec();
+        ParseRequestError::ParseRequestBody {
+            reason: err.into_message(),
+            violations,
+        }
+    })
+}
+
+/// Encodes the value, a failure is returned as an `application/problem+json`
+/// response in the same way as [`ParseRequestError`].
+pub(crate) fn into_response<C: Codec, T: ToJSON>(value: &T) -> Response {
+    match C::encode(&value.to_json()) {
+        Ok(data) => Response::builder().content_type(C::CONTENT_TYPE).body(data),
+        Err(reason) => problem_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            reason,
+            serde_json::Map::new(),
+        ),
+    }
+}
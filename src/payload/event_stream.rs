@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt};
+use poem::{
+    web::sse::{Event, SSE},
+    IntoResponse, Request, Response,
+};
+
+use crate::{
+    payload::ResponseContent,
+    registry::{MetaMediaType, MetaSchema, MetaSchemaRef, Registry},
+    types::ToJSON,
+    ParseRequestError,
+};
+
+/// A `text/event-stream` payload, each item of the stream is sent as a
+/// Server-Sent Event whose data is the JSON representation of the item.
+///
+/// # Examples
+///
+/// ```
+/// use futures_util::{stream::BoxStream, StreamExt};
+/// use poem_openapi::{payload::EventStream, Object, OpenApi};
+///
+/// #[derive(Object)]
+/// struct Progress {
+///     percent: u8,
+/// }
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/progress", method = "get")]
+///     async fn progress(&self) -> EventStream<BoxStream<'static, Progress>> {
+///         EventStream::new(
+///             futures_util::stream::iter((0..=100).map(|percent| Progress { percent })).boxed(),
+///         )
+///     }
+/// }
+/// ```
+pub struct EventStream<S> {
+    stream: S,
+    keep_alive: Option<Duration>,
+}
+
+impl<S> EventStream<S> {
+    /// Create an event stream payload.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            keep_alive: None,
+        }
+    }
+
+    /// Set the keep alive interval.
+    #[must_use]
+    pub fn keep_alive(self, duration: Duration) -> Self {
+        Self {
+            keep_alive: Some(duration),
+            ..self
+        }
+    }
+}
+
+impl<S, T> IntoResponse for EventStream<S>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: ToJSON,
+{
+    fn into_response(self) -> Response {
+        let mut sse = SSE::new(
+            self.stream
+                .map(|item| Event::message(item.to_json().to_string())),
+        );
+        if let Some(duration) = self.keep_alive {
+            sse = sse.keep_alive(duration);
+        }
+        sse.into_response()
+    }
+}
+
+impl<S, T> ResponseContent for EventStream<S>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: ToJSON,
+{
+    fn media_types() -> Vec<MetaMediaType> {
+        vec![MetaMediaType {
+            content_type: "text/event-stream",
+            schema: MetaSchemaRef::Inline(MetaSchema {
+                items: Some(Box::new(T::schema_ref())),
+                ..MetaSchema::new("array")
+            }),
+        }]
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
+    fn into_response_with_request(
+        self,
+        _request: Option<&Request>,
+    ) -> Result<Response, ParseRequestError> {
+        Ok(self.into_response())
+    }
+}
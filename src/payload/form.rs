@@ -0,0 +1,152 @@
+use poem::{FromRequest, Request, RequestBody};
+use serde_json::{Map, Value};
+
+use crate::{
+    payload::{
+        schema::{check_content_type, convert_value, properties, registry, resolve},
+        Payload,
+    },
+    registry::{MetaSchema, MetaSchemaRef, Registry},
+    types::{IsObjectType, ParseFromJSON},
+    ParseRequestError,
+};
+
+/// A `application/x-www-form-urlencoded` payload.
+///
+/// `T` must be an [`Object`](crate::Object), the values of the form are
+/// converted according to the schema of its fields, and then validated in the
+/// same way as a JSON payload.
+///
+/// # Examples
+///
+/// ```
+/// use poem_openapi::{payload::Form, Object, OpenApi};
+///
+/// #[derive(Object)]
+/// #[oai(rename_fields = "snake_case")]
+/// struct TokenRequest {
+///     grant_type: String,
+///     #[oai(max_length = 32)]
+///     username: String,
+///     password: String,
+/// }
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/token", method = "post")]
+///     async fn token(&self, req: Form<TokenRequest>) {}
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Form<T>(pub T);
+
+#[poem::async_trait]
+impl<T: IsObjectType + ParseFromJSON + 'static> Payload for Form<T> {
+    const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
+
+    fn schema_ref() -> MetaSchemaRef {
+        T::schema_ref()
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
+    async fn from_request(
+        request: &Request,
+        body: &mut RequestBody,
+    ) -> Result<Self, ParseRequestError> {
+        check_content_type(request, Self::CONTENT_TYPE)?;
+
+        let data = <Vec<u8>>::from_request(request, body)
+            .await
+            .map_err(|err| ParseRequestError::ParseRequestBody {
+                reason: err.to_string(),
+                violations: Vec::new(),
+            })?;
+        let pairs: Vec<(String, String)> = serde_urlencoded::from_bytes(&data).map_err(|err| {
+            ParseRequestError::ParseRequestBody {
+                reason: err.to_string(),
+                violations: Vec::new(),
+            }
+        })?;
+
+        let value = pairs_to_json(registry::<T>(), &T::schema_ref(), pairs);
+
+        let value = T::parse_from_json(value).map_err(|err| {
+            let violations = err.violations().to_vec();
+            ParseRequestError::ParseRequestBody {
+                reason: err.into_message(),
+                violations,
+            }
+        })?;
+        Ok(Self(value))
+    }
+}
+
+/// Converts the form pairs to a JSON object according to the schema.
+///
+/// The array properties that do not appear in the form are empty arrays,
+/// because HTML forms cannot represent an empty list, and the empty values of
+/// the properties that are not strings are ignored, such as an empty
+/// `<input type="number">`.
+fn pairs_to_json(
+    registry: &Registry,
+    schema: &MetaSchemaRef,
+    pairs: Vec<(String, String)>,
+) -> Value {
+    let mut all_properties = Vec::new();
+    if let Some(schema) = resolve(registry, schema) {
+        properties(registry, schema, &mut all_properties);
+    }
+    let property = |name: &str| {
+        all_properties
+            .iter()
+            .find(|(property, _)| *property == name)
+            .and_then(|(_, schema)| resolve(registry, schema))
+    };
+
+    let is_unset = |schema: Option<&MetaSchema>, value: &str| {
+        value.is_empty()
+            && schema
+                .map(|schema| schema.ty != "string")
+                .unwrap_or_default()
+    };
+
+    let mut obj = Map::new();
+    for (name, value) in pairs {
+        match property(&name) {
+            Some(property) if property.ty == "array" => {
+                let items = property
+                    .items
+                    .as_deref()
+                    .and_then(|items| resolve(registry, items));
+                if is_unset(items, &value) {
+                    continue;
+                }
+                let value = convert_value(items, value);
+                if let Value::Array(values) =
+                    obj.entry(name).or_insert_with(|| Value::Array(Vec::new()))
+                {
+                    values.push(value);
+                }
+            }
+            property if is_unset(property, &value) => {}
+            property => {
+                obj.entry(name)
+                    .or_insert_with(|| convert_value(property, value));
+            }
+        }
+    }
+
+    for (name, schema) in &all_properties {
+        if resolve(registry, schema).map(|schema| schema.ty) == Some("array") {
+            obj.entry(name.to_string())
+                .or_insert_with(|| Value::Array(Vec::new()));
+        }
+    }
+
+    Value::Object(obj)
+}
@@ -12,6 +12,12 @@ use crate::{
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Json<T>(pub T);
 
+impl<T> From<T> for Json<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
 #[poem::async_trait]
 impl<T: ParseFromJSON> Payload for Json<T> {
     const CONTENT_TYPE: &'static str = "application/json";
@@ -33,11 +39,15 @@ impl<T: ParseFromJSON> Payload for Json<T> {
             .await
             .map_err(|err| ParseRequestError::ParseRequestBody {
                 reason: err.to_string(),
+                violations: Vec::new(),
             })?;
-        let value =
-            T::parse_from_json(value.0).map_err(|err| ParseRequestError::ParseRequestBody {
+        let value = T::parse_from_json(value.0).map_err(|err| {
+            let violations = err.violations().to_vec();
+            ParseRequestError::ParseRequestBody {
                 reason: err.into_message(),
-            })?;
+                violations,
+            }
+        })?;
         Ok(Self(value))
     }
 }
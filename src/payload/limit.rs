@@ -0,0 +1,107 @@
+use std::{
+    io::Error as IoError,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use poem::{http::header, Body, Request, RequestBody};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::ParseRequestError;
+
+/// A reader that fails when more than `limit` bytes are read.
+struct LimitedReader<R> {
+    inner: R,
+    remaining: usize,
+    limit: usize,
+    exceeded: Arc<AtomicBool>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for LimitedReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let filled = buf.filled().len();
+        match Pin::new(&mut self.inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                let n = buf.filled().len() - filled;
+                if n > self.remaining {
+                    self.exceeded.store(true, Ordering::SeqCst);
+                    return Poll::Ready(Err(IoError::other(ParseRequestError::PayloadTooLarge {
+                        limit: self.limit,
+                    })));
+                }
+                self.remaining -= n;
+                Poll::Ready(Ok(()))
+            }
+            res => res,
+        }
+    }
+}
+
+/// Limits the size of the request body of an operation.
+///
+/// If the `Content-Length` header exceeds the limit, the error is returned
+/// before reading the body. A streaming payload is read by the operation, so
+/// the limit is checked again after the operation returns.
+#[doc(hidden)]
+pub struct BodyLimit {
+    limit: usize,
+    exceeded: Arc<AtomicBool>,
+}
+
+impl BodyLimit {
+    /// Create a limit of `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            exceeded: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Parse the request object from the HTTP request, the body is rejected
+    /// if it is larger than the limit.
+    pub async fn from_request<T: crate::Request>(
+        &self,
+        request: &Request,
+        body: &mut RequestBody,
+    ) -> Result<T, ParseRequestError> {
+        let limit = self.limit;
+        let content_length = request
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        if matches!(content_length, Some(content_length) if content_length > limit as u64) {
+            return Err(ParseRequestError::PayloadTooLarge { limit });
+        }
+
+        if let Ok(inner) = body.take() {
+            *body = RequestBody::new(Body::from_async_read(LimitedReader {
+                inner: inner.into_async_read(),
+                remaining: limit,
+                limit,
+                exceeded: self.exceeded.clone(),
+            }));
+        }
+
+        let res = T::from_request(request, body).await;
+        self.check()?;
+        res
+    }
+
+    /// Returns `ParseRequestError::PayloadTooLarge` if more than `limit`
+    /// bytes of the body have been read.
+    pub fn check(&self) -> Result<(), ParseRequestError> {
+        if self.exceeded.load(Ordering::SeqCst) {
+            return Err(ParseRequestError::PayloadTooLarge { limit: self.limit });
+        }
+        Ok(())
+    }
+}
@@ -1,16 +1,44 @@
 //! Commonly used payload types.
 
+mod attachment;
 mod binary;
+mod binary_stream;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+mod codec;
+mod event_stream;
+mod form;
 mod json;
+mod limit;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod ndjson;
+mod negotiate;
 mod plain_text;
+pub(crate) mod schema;
+mod xml;
 
+pub use attachment::{Attachment, AttachmentType};
 pub use binary::Binary;
+pub use binary_stream::BinaryStream;
+#[cfg(feature = "cbor")]
+pub use cbor::Cbor;
+pub use event_stream::EventStream;
+pub use form::Form;
 pub use json::Json;
+#[doc(hidden)]
+pub use limit::BodyLimit;
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgPack;
+pub use ndjson::NdJson;
+pub use negotiate::Negotiate;
 pub use plain_text::PlainText;
-use poem::{Request, RequestBody};
+use poem::{IntoResponse, Request, RequestBody, Response};
+pub use xml::Xml;
 
 use crate::{
-    registry::{MetaSchemaRef, Registry},
+    registry::{MetaHeader, MetaMediaType, MetaResponse, MetaSchemaRef, Registry},
     ParseRequestError,
 };
 
@@ -52,3 +80,62 @@ impl<T: Payload> Payload for poem::Result<T> {
         }
     }
 }
+
+/// Represents the body of a response, which can be offered in one or more
+/// media types.
+pub trait ResponseContent: Sized {
+    /// If true, the media type is selected by the `Accept` header of the
+    /// request, and `406 Not Acceptable` is returned if none is accepted.
+    const NEGOTIATED: bool = false;
+
+    /// Gets the media types of this content.
+    fn media_types() -> Vec<MetaMediaType>;
+
+    /// Register the schema contained in this content to the registry.
+    fn register(registry: &mut Registry);
+
+    /// Gets the headers of the response containing this content.
+    fn headers() -> Vec<MetaHeader> {
+        vec![]
+    }
+
+    /// Gets the other responses that may be returned instead, such as `206
+    /// Partial Content`.
+    fn extra_responses() -> Vec<MetaResponse> {
+        vec![]
+    }
+
+    /// Checks whether this content can be converted into a response accepted
+    /// by the request, it is called before the operation is invoked.
+    #[allow(unused_variables)]
+    fn check_accept(request: &Request) -> Result<(), ParseRequestError> {
+        Ok(())
+    }
+
+    /// Convert this content into a response to the request, such as selecting
+    /// the media type by the `Accept` header.
+    fn into_response_with_request(
+        self,
+        request: Option<&Request>,
+    ) -> Result<Response, ParseRequestError>;
+}
+
+impl<T: Payload + IntoResponse> ResponseContent for T {
+    fn media_types() -> Vec<MetaMediaType> {
+        vec![MetaMediaType {
+            content_type: T::CONTENT_TYPE,
+            schema: T::schema_ref(),
+        }]
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
+    fn into_response_with_request(
+        self,
+        _request: Option<&Request>,
+    ) -> Result<Response, ParseRequestError> {
+        Ok(self.into_response())
+    }
+}
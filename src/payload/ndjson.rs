@@ -0,0 +1,99 @@
+use std::io::{Cursor, Error as IoError};
+
+use futures_util::{Stream, StreamExt};
+use poem::{Body, IntoResponse, Request, Response};
+use tokio_util::io::StreamReader;
+
+use crate::{
+    payload::ResponseContent,
+    registry::{MetaMediaType, MetaSchema, MetaSchemaRef, Registry},
+    types::ToJSON,
+    ParseRequestError,
+};
+
+/// A `application/x-ndjson` payload, each item of the stream is sent as a
+/// line of JSON as soon as it is produced.
+///
+/// # Examples
+///
+/// ```
+/// use futures_util::{stream::BoxStream, StreamExt};
+/// use poem_openapi::{payload::NdJson, Object, OpenApi};
+///
+/// #[derive(Object)]
+/// struct User {
+///     id: i64,
+///     name: String,
+/// }
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/users/export", method = "get")]
+///     async fn export(&self) -> NdJson<BoxStream<'static, User>> {
+///         NdJson::new(
+///             futures_util::stream::iter((0..1000).map(|id| User {
+///                 id,
+///                 name: format!("user{}", id),
+///             }))
+///             .boxed(),
+///         )
+///     }
+/// }
+/// ```
+pub struct NdJson<S> {
+    stream: S,
+}
+
+impl<S> NdJson<S> {
+    /// Create a NDJSON payload.
+    pub fn new(stream: S) -> Self {
+        Self { stream }
+    }
+}
+
+impl<S, T> IntoResponse for NdJson<S>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: ToJSON,
+{
+    fn into_response(self) -> Response {
+        let stream = self.stream.map(|item| {
+            let mut line = item.to_json().to_string();
+            line.push('\n');
+            Ok::<_, IoError>(Cursor::new(line.into_bytes()))
+        });
+
+        Response::builder()
+            .content_type("application/x-ndjson")
+            .body(Body::from_async_read(StreamReader::new(stream)))
+    }
+}
+
+impl<S, T> ResponseContent for NdJson<S>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: ToJSON,
+{
+    fn media_types() -> Vec<MetaMediaType> {
+        vec![MetaMediaType {
+            content_type: "application/x-ndjson",
+            schema: MetaSchemaRef::Inline(MetaSchema {
+                items: Some(Box::new(T::schema_ref())),
+                ..MetaSchema::new("array")
+            }),
+        }]
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
+    fn into_response_with_request(
+        self,
+        _request: Option<&Request>,
+    ) -> Result<Response, ParseRequestError> {
+        Ok(self.into_response())
+    }
+}
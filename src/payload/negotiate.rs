@@ -0,0 +1,215 @@
+use std::marker::PhantomData;
+
+use poem::{http::header, IntoResponse, Request, Response};
+
+use crate::{
+    payload::{Payload, ResponseContent},
+    registry::{MetaMediaType, Registry},
+    ParseRequestError,
+};
+
+/// A payload that is converted to one of the payload types `P` according to
+/// the `Accept` header of the request.
+///
+/// `P` is a tuple of payload types, such as `(Json<T>, PlainText)`. The first
+/// one is used if the request does not have an `Accept` header, and a
+/// `406 Not Acceptable` error is returned if none of them is accepted.
+///
+/// # Examples
+///
+/// ```
+/// use poem_openapi::{
+///     payload::{Json, Negotiate, PlainText},
+///     OpenApi,
+/// };
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/hello", method = "get")]
+///     async fn hello(&self) -> Negotiate<String, (Json<String>, PlainText)> {
+///         Negotiate::new("hello".to_string())
+///     }
+/// }
+/// ```
+pub struct Negotiate<T, P> {
+    value: T,
+    _mark: PhantomData<fn() -> P>,
+}
+
+impl<T, P> Negotiate<T, P> {
+    /// Create a negotiated payload.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Consumes this payload and returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, P> From<T> for Negotiate<T, P> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Send, P> IntoResponse for Negotiate<T, P>
+where
+    Self: ResponseContent,
+{
+    fn into_response(self) -> Response {
+        match self.into_response_with_request(None) {
+            Ok(resp) => resp,
+            Err(err) => err.into_response(),
+        }
+    }
+}
+
+/// Returns the media type without parameters, such as `charset`.
+fn essence(media_type: &str) -> &str {
+    media_type.split(';').next().unwrap_or_default().trim()
+}
+
+/// Returns the quality of `content_type` in the `Accept` header and the
+/// specificity of the media range that matches it, `None` means that the
+/// content type is not accepted.
+fn quality(accept: &str, content_type: &str) -> Option<(f32, u8)> {
+    let (ty, subtype) = essence(content_type).split_once('/')?;
+    let mut best: Option<(u8, f32)> = None;
+
+    for range in accept.split(',') {
+        let mut parts = range.split(';');
+        let media_range = parts.next().unwrap_or_default().trim();
+        let (range_ty, range_subtype) = match media_range.split_once('/') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let specificity = match (range_ty, range_subtype) {
+            ("*", "*") => 0,
+            (range_ty, "*") if range_ty.eq_ignore_ascii_case(ty) => 1,
+            (range_ty, range_subtype)
+                if range_ty.eq_ignore_ascii_case(ty)
+                    && range_subtype.eq_ignore_ascii_case(subtype) =>
+            {
+                2
+            }
+            _ => continue,
+        };
+        let q = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        if best.map(|(s, _)| specificity > s).unwrap_or(true) {
+            best = Some((specificity, q));
+        }
+    }
+
+    best.map(|(specificity, q)| (q, specificity))
+        .filter(|(q, _)| *q > 0.0)
+}
+
+/// Selects the index of the content type accepted by the request, or returns
+/// `ParseRequestError::NotAcceptable`.
+fn select_for_request(
+    request: Option<&Request>,
+    content_types: &[&str],
+) -> Result<usize, ParseRequestError> {
+    let accept = request
+        .and_then(|request| request.headers().get(header::ACCEPT))
+        .and_then(|value| value.to_str().ok());
+    select_content_type(accept, content_types).ok_or_else(|| ParseRequestError::NotAcceptable {
+        accept: accept.unwrap_or_default().to_string(),
+    })
+}
+
+/// Selects the index of the content type with the highest quality in the
+/// `Accept` header. If several of them have the same quality, the one matched
+/// by the most specific media range wins, and then the earlier one.
+pub(crate) fn select_content_type(accept: Option<&str>, content_types: &[&str]) -> Option<usize> {
+    let accept = match accept {
+        Some(accept) if !accept.trim().is_empty() => accept,
+        _ => {
+            return if content_types.is_empty() {
+                None
+            } else {
+                Some(0)
+            }
+        }
+    };
+
+    let mut selected: Option<(usize, f32, u8)> = None;
+    for (idx, content_type) in content_types.iter().enumerate() {
+        if let Some((q, specificity)) = quality(accept, content_type) {
+            if selected
+                .map(|(_, selected_q, selected_specificity)| {
+                    q > selected_q || (q == selected_q && specificity > selected_specificity)
+                })
+                .unwrap_or(true)
+            {
+                selected = Some((idx, q, specificity));
+            }
+        }
+    }
+    selected.map(|(idx, _, _)| idx)
+}
+
+macro_rules! impl_negotiate {
+    ($($p:ident),*) => {
+        impl<T, $($p),*> ResponseContent for Negotiate<T, ($($p,)*)>
+        where
+            $($p: Payload + IntoResponse + From<T>),*
+        {
+            const NEGOTIATED: bool = true;
+
+            fn media_types() -> Vec<MetaMediaType> {
+                vec![$(MetaMediaType {
+                    content_type: $p::CONTENT_TYPE,
+                    schema: $p::schema_ref(),
+                }),*]
+            }
+
+            fn register(registry: &mut Registry) {
+                $($p::register(registry);)*
+            }
+
+            fn check_accept(request: &Request) -> Result<(), ParseRequestError> {
+                select_for_request(Some(request), &[$($p::CONTENT_TYPE),*]).map(|_| ())
+            }
+
+            #[allow(unused_assignments)]
+            fn into_response_with_request(
+                self,
+                request: Option<&Request>,
+            ) -> Result<Response, ParseRequestError> {
+                let idx = select_for_request(request, &[$($p::CONTENT_TYPE),*])?;
+
+                let mut current = 0;
+                $(
+                    if idx == current {
+                        let mut resp = $p::from(self.value).into_response();
+                        resp.headers_mut().insert(header::VARY, header::HeaderValue::from_static("accept"));
+                        return Ok(resp);
+                    }
+                    current += 1;
+                )*
+                unreachable!()
+            }
+        }
+    };
+}
+
+impl_negotiate!(P1);
+impl_negotiate!(P1, P2);
+impl_negotiate!(P1, P2, P3);
+impl_negotiate!(P1, P2, P3, P4);
+impl_negotiate!(P1, P2, P3, P4, P5);
+impl_negotiate!(P1, P2, P3, P4, P5, P6);
+impl_negotiate!(P1, P2, P3, P4, P5, P6, P7);
+impl_negotiate!(P1, P2, P3, P4, P5, P6, P7, P8);
@@ -27,6 +27,7 @@ impl Payload for PlainText {
         Ok(Self(String::from_request(request, body).await.map_err(
             |err| ParseRequestError::ParseRequestBody {
                 reason: err.to_string(),
+                violations: Vec::new(),
             },
         )?))
     }
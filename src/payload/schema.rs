@@ -0,0 +1,90 @@
+//! Helpers for the payloads that are converted to JSON according to the
+//! schema of the type, such as [`Form`](super::Form) and [`Xml`](super::Xml).
+
+use poem::{http::header, Request};
+use serde_json::Value;
+
+use crate::{
+    registry::{MetaSchema, MetaSchemaRef, Registry},
+    types::Type,
+    utils::TypeCache,
+    ParseRequestError,
+};
+
+/// Returns the registry containing the schemas of `T`, it is only built once
+/// for each type.
+pub(crate) fn registry<T: Type + 'static>() -> &'static Registry {
+    static REGISTRIES: TypeCache<Registry> = TypeCache::new();
+
+    REGISTRIES.get_or_init::<T>(|| {
+        let mut registry = Registry::new();
+        T::register(&mut registry);
+        Box::new(registry)
+    })
+}
+
+/// Checks that the media type of the request is `expected`.
+pub(crate) fn check_content_type(
+    request: &Request,
+    expected: &str,
+) -> Result<(), ParseRequestError> {
+    let content_type = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(ParseRequestError::ExpectContentType)?;
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    if !essence.eq_ignore_ascii_case(expected) {
+        return Err(ParseRequestError::ContentTypeNotSupported {
+            content_type: content_type.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn resolve<'a>(
+    registry: &'a Registry,
+    schema: &'a MetaSchemaRef,
+) -> Option<&'a MetaSchema> {
+    let schema = match schema {
+        MetaSchemaRef::Inline(schema) => schema,
+        MetaSchemaRef::Reference(name) => registry.schemas.get(name)?,
+    };
+    if schema.ty.is_empty() && schema.all_of.len() == 1 {
+        resolve(registry, &schema.all_of[0])
+    } else {
+        Some(schema)
+    }
+}
+
+pub(crate) fn properties<'a>(
+    registry: &'a Registry,
+    schema: &'a MetaSchema,
+    properties: &mut Vec<(&'static str, &'a MetaSchemaRef)>,
+) {
+    properties.extend(
+        schema
+            .properties
+            .iter()
+            .map(|(name, schema)| (*name, schema)),
+    );
+    for schema in &schema.all_of {
+        if let Some(schema) = resolve(registry, schema) {
+            self::properties(registry, schema, properties);
+        }
+    }
+}
+
+pub(crate) fn convert_value(schema: Option<&MetaSchema>, value: String) -> Value {
+    let converted = match schema.map(|schema| schema.ty) {
+        Some("integer") => value.parse::<i64>().ok().map(Value::from),
+        Some("number") => value.parse::<f64>().ok().map(Value::from),
+        Some("boolean") => match value.as_str() {
+            "true" | "on" => Some(Value::Bool(true)),
+            "false" | "off" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    };
+    converted.unwrap_or(Value::String(value))
+}
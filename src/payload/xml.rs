@@ -0,0 +1,475 @@
+use poem::{http::header, FromRequest, IntoResponse, Request, RequestBody, Response};
+use quick_xml::{events::Event, Reader};
+use serde_json::{Map, Value};
+
+use crate::{
+    payload::{
+        schema::{check_content_type, convert_value, properties, registry, resolve},
+        Payload,
+    },
+    registry::{MetaSchema, MetaSchemaRef, MetaXml, Registry},
+    types::{ParseFromJSON, ToJSON},
+    ParseRequestError,
+};
+
+/// A XML payload.
+///
+/// `T` is usually an [`Object`](crate::Object), the document is converted
+/// according to the schema of its fields, and then validated in the same way
+/// as a JSON payload. Use the `xml` attribute of the fields to change how they
+/// are represented. The entries of a map are represented as
+/// `<entry key="...">` elements, because the keys may not be valid XML names.
+///
+/// # Examples
+///
+/// ```
+/// use poem_openapi::{payload::Xml, Object, OpenApi};
+///
+/// #[derive(Object)]
+/// struct Pet {
+///     #[oai(xml(attribute))]
+///     id: i64,
+///     name: String,
+///     #[oai(xml(name = "tag", wrapped))]
+///     tags: Vec<String>,
+/// }
+///
+/// struct Api;
+///
+/// #[OpenApi]
+/// impl Api {
+///     #[oai(path = "/pet", method = "post")]
+///     async fn create(&self, pet: Xml<Pet>) -> Xml<Pet> {
+///         pet
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Xml<T>(pub T);
+
+impl<T> From<T> for Xml<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+#[poem::async_trait]
+impl<T: ParseFromJSON + 'static> Payload for Xml<T> {
+    const CONTENT_TYPE: &'static str = "application/xml";
+
+    fn schema_ref() -> MetaSchemaRef {
+        T::schema_ref()
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
+    async fn from_request(
+        request: &Request,
+        body: &mut RequestBody,
+    ) -> Result<Self, ParseRequestError> {
+        check_content_type(request, Self::CONTENT_TYPE)?;
+
+        let data = String::from_request(request, body).await.map_err(|err| {
+            ParseRequestError::ParseRequestBody {
+                reason: err.to_string(),
+                violations: Vec::new(),
+            }
+        })?;
+        let element =
+            Element::parse(&data).map_err(|reason| ParseRequestError::ParseRequestBody {
+                reason,
+                violations: Vec::new(),
+            })?;
+
+        let value = element_to_json(registry::<T>(), &T::schema_ref(), &element);
+
+        let value = T::parse_from_json(value).map_err(|err| {
+            let violations = err.violations().to_vec();
+            ParseRequestError::ParseRequestBody {
+                reason: err.into_message(),
+                violations,
+            }
+        })?;
+        Ok(Self(value))
+    }
+}
+
+impl<T: ToJSON + 'static> IntoResponse for Xml<T> {
+    fn into_response(self) -> Response {
+        let registry = registry::<T>();
+        let schema_ref = T::schema_ref();
+        let name = match resolve(registry, &schema_ref).and_then(|schema| schema.xml.as_ref()) {
+            Some(MetaXml {
+                name: Some(name), ..
+            }) => name,
+            _ => match &schema_ref {
+                MetaSchemaRef::Reference(name) => name,
+                MetaSchemaRef::Inline(_) => "value",
+            },
+        };
+
+        let mut body = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        write_element(
+            registry,
+            &schema_ref,
+            None,
+            name,
+            None,
+            &self.0.to_json(),
+            &mut body,
+        );
+
+        Response::builder()
+            .header(header::CONTENT_TYPE, "application/xml")
+            .body(body)
+    }
+}
+
+/// A XML element, only the local names of the elements and attributes are
+/// kept.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn parse(data: &str) -> Result<Element, String> {
+        let mut reader = Reader::from_str(data);
+        reader.trim_text(true).expand_empty_elements(true);
+
+        let mut buf = Vec::new();
+        let mut stack: Vec<Element> = Vec::new();
+        loop {
+            match reader.read_event(&mut buf).map_err(|err| err.to_string())? {
+                Event::Start(start) => {
+                    let mut element = Element {
+                        name: decode(&reader, start.local_name())?,
+                        ..Default::default()
+                    };
+                    for attr in start.attributes() {
+                        let attr = attr.map_err(|err| err.to_string())?;
+                        let key = attr.key;
+                        if key == b"xmlns" || key.starts_with(b"xmlns:") {
+                            continue;
+                        }
+                        let local_name = match key.iter().position(|c| *c == b':') {
+                            Some(idx) => &key[idx + 1..],
+                            None => key,
+                        };
+                        let value = attr.unescaped_value().map_err(|err| err.to_string())?;
+                        element
+                            .attributes
+                            .push((decode(&reader, local_name)?, decode(&reader, &value)?));
+                    }
+                    stack.push(element);
+                }
+                Event::End(_) => {
+                    let element = stack.pop().ok_or("unexpected end tag")?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                Event::Text(text) | Event::CData(text) => {
+                    if let Some(element) = stack.last_mut() {
+                        let text = text
+                            .unescape_and_decode(&reader)
+                            .map_err(|err| err.to_string())?;
+                        element.text.push_str(&text);
+                    }
+                }
+                Event::Eof => return Err("expect a root element".to_string()),
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children
+            .iter()
+            .filter(move |element| element.name == name)
+    }
+}
+
+fn decode<B: std::io::BufRead>(reader: &Reader<B>, data: &[u8]) -> Result<String, String> {
+    reader
+        .decode(data)
+        .map(ToString::to_string)
+        .map_err(|err| err.to_string())
+}
+
+/// Returns the XML hints of a schema, they are stored in the inline schema of
+/// the property.
+fn xml_of(schema: &MetaSchemaRef) -> Option<&MetaXml> {
+    match schema {
+        MetaSchemaRef::Inline(schema) => schema.xml.as_ref(),
+        MetaSchemaRef::Reference(_) => None,
+    }
+}
+
+fn is_array(schema: Option<&MetaSchema>) -> bool {
+    schema.map(|schema| schema.ty) == Some("array")
+}
+
+/// Converts a XML element to a JSON value according to the schema.
+fn element_to_json(registry: &Registry, schema: &MetaSchemaRef, element: &Element) -> Value {
+    let resolved = resolve(registry, schema);
+
+    if is_array(resolved) {
+        let items = resolved.and_then(|schema| schema.items.as_deref());
+        return Value::Array(
+            element
+                .children
+                .iter()
+                .map(|child| match items {
+                    Some(items) => element_to_json(registry, items, child),
+                    None => Value::String(child.text.clone()),
+                })
+                .collect(),
+        );
+    }
+
+    let mut all_properties = Vec::new();
+    if let Some(schema) = resolved {
+        properties(registry, schema, &mut all_properties);
+    }
+
+    if !all_properties.is_empty() {
+        let mut obj = Map::new();
+        for (name, schema) in all_properties {
+            let xml = xml_of(schema);
+            let element_name = xml.and_then(|xml| xml.name).unwrap_or(name);
+            let property = resolve(registry, schema);
+
+            if xml.map(|xml| xml.attribute).unwrap_or_default() {
+                if let Some(value) = element.attribute(element_name) {
+                    obj.insert(name.to_string(), convert_value(property, value.to_string()));
+                }
+            } else if is_array(property) {
+                let items = property.and_then(|schema| schema.items.as_deref());
+                let convert = |child: &Element| match items {
+                    Some(items) => element_to_json(registry, items, child),
+                    None => Value::String(child.text.clone()),
+                };
+                if xml.map(|xml| xml.wrapped).unwrap_or_default() {
+                    if let Some(wrapper) = element.children(element_name).next() {
+                        obj.insert(
+                            name.to_string(),
+                            Value::Array(wrapper.children.iter().map(convert).collect()),
+                        );
+                    }
+                } else {
+                    obj.insert(
+                        name.to_string(),
+                        Value::Array(element.children(element_name).map(convert).collect()),
+                    );
+                }
+            } else if let Some(child) = element.children(element_name).next() {
+                obj.insert(name.to_string(), element_to_json(registry, schema, child));
+            }
+        }
+        return Value::Object(obj);
+    }
+
+    if let Some(additional_properties) =
+        resolved.and_then(|schema| schema.additional_properties.as_ref()?.schema())
+    {
+        return Value::Object(
+            element
+                .children
+                .iter()
+                .filter(|child| child.name == MAP_ENTRY)
+                .filter_map(|child| {
+                    Some((
+                        child.attribute(MAP_KEY)?.to_string(),
+                        element_to_json(registry, additional_properties, child),
+                    ))
+                })
+                .collect(),
+        );
+    }
+
+    convert_value(resolved, element.text.clone())
+}
+
+/// The name of the elements of the map entries.
+const MAP_ENTRY: &str = "entry";
+
+/// The name of the attribute holding the key of a map entry, the keys are not
+/// used as element names because they may not be valid XML names.
+const MAP_KEY: &str = "key";
+
+fn escape(value: &str, out: &mut String) {
+    for c in value.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::String(value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn start_tag(xml: Option<&MetaXml>, name: &str, key: Option<&str>, out: &mut String) -> String {
+    let name = match xml.and_then(|xml| xml.prefix) {
+        Some(prefix) => format!("{}:{}", prefix, name),
+        None => name.to_string(),
+    };
+    out.push('<');
+    out.push_str(&name);
+    if let Some(namespace) = xml.and_then(|xml| xml.namespace) {
+        match xml.and_then(|xml| xml.prefix) {
+            Some(prefix) => out.push_str(&format!(" xmlns:{}=\"", prefix)),
+            None => out.push_str(" xmlns=\""),
+        }
+        escape(namespace, out);
+        out.push('"');
+    }
+    if let Some(key) = key {
+        out.push_str(&format!(" {}=\"", MAP_KEY));
+        escape(key, out);
+        out.push('"');
+    }
+    name
+}
+
+/// Writes a JSON value as a XML element according to the schema, `null`
+/// values are omitted.
+fn write_element(
+    registry: &Registry,
+    schema: &MetaSchemaRef,
+    xml: Option<&MetaXml>,
+    name: &str,
+    key: Option<&str>,
+    value: &Value,
+    out: &mut String,
+) {
+    let resolved = resolve(registry, schema);
+
+    match value {
+        Value::Null => {}
+        Value::Array(values) => {
+            let items = resolved
+                .and_then(|schema| schema.items.as_deref())
+                .cloned()
+                .unwrap_or(MetaSchemaRef::Inline(MetaSchema::ANY));
+            let item_name = xml_of(&items).and_then(|xml| xml.name).unwrap_or(name);
+            let tag = start_tag(xml, name, key, out);
+            out.push('>');
+            for value in values {
+                write_element(
+                    registry,
+                    &items,
+                    xml_of(&items),
+                    item_name,
+                    None,
+                    value,
+                    out,
+                );
+            }
+            out.push_str(&format!("</{}>", tag));
+        }
+        Value::Object(obj) => {
+            let mut all_properties = Vec::new();
+            if let Some(schema) = resolved {
+                properties(registry, schema, &mut all_properties);
+            }
+
+            let tag = start_tag(xml, name, key, out);
+            for (property_name, schema) in &all_properties {
+                let xml = xml_of(schema);
+                if !xml.map(|xml| xml.attribute).unwrap_or_default() {
+                    continue;
+                }
+                if let Some(value) = obj.get(*property_name).and_then(scalar_to_string) {
+                    let attribute_name = xml.and_then(|xml| xml.name).unwrap_or(property_name);
+                    out.push(' ');
+                    if let Some(prefix) = xml.and_then(|xml| xml.prefix) {
+                        out.push_str(prefix);
+                        out.push(':');
+                    }
+                    out.push_str(attribute_name);
+                    out.push_str("=\"");
+                    escape(&value, out);
+                    out.push('"');
+                }
+            }
+            out.push('>');
+
+            if all_properties.is_empty() {
+                let additional_properties = resolved
+                    .and_then(|schema| schema.additional_properties.as_ref()?.schema())
+                    .cloned()
+                    .unwrap_or(MetaSchemaRef::Inline(MetaSchema::ANY));
+                for (key, value) in obj {
+                    write_element(
+                        registry,
+                        &additional_properties,
+                        None,
+                        MAP_ENTRY,
+                        Some(key),
+                        value,
+                        out,
+                    );
+                }
+            }
+
+            for (property_name, schema) in &all_properties {
+                let xml = xml_of(schema);
+                if xml.map(|xml| xml.attribute).unwrap_or_default() {
+                    continue;
+                }
+                let value = match obj.get(*property_name) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                let element_name = xml.and_then(|xml| xml.name).unwrap_or(property_name);
+
+                match value {
+                    Value::Array(values) if !xml.map(|xml| xml.wrapped).unwrap_or_default() => {
+                        let items = resolve(registry, schema)
+                            .and_then(|schema| schema.items.as_deref())
+                            .cloned()
+                            .unwrap_or(MetaSchemaRef::Inline(MetaSchema::ANY));
+                        for value in values {
+                            write_element(registry, &items, xml, element_name, None, value, out);
+                        }
+                    }
+                    _ => write_element(registry, schema, xml, element_name, None, value, out),
+                }
+            }
+
+            out.push_str(&format!("</{}>", tag));
+        }
+        value => {
+            let tag = start_tag(xml, name, key, out);
+            out.push('>');
+            escape(&scalar_to_string(value).unwrap_or_default(), out);
+            out.push_str(&format!("</{}>", tag));
+        }
+    }
+}
@@ -18,10 +18,15 @@ fn is_false(value: &bool) -> bool {
     !*value
 }
 
+#[inline]
+fn is_empty_str(value: &&str) -> bool {
+    value.is_empty()
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MetaSchema {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", skip_serializing_if = "is_empty_str")]
     pub ty: &'static str,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<&'static str>,
@@ -41,10 +46,28 @@ pub struct MetaSchema {
     pub properties: Vec<(&'static str, MetaSchemaRef)>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<MetaSchemaRef>>,
+    /// The schemas of the items of a tuple, written as `prefixItems` in
+    /// OpenAPI 3.1 documents.
+    #[serde(rename = "x-prefixItems", skip_serializing_if = "Vec::is_empty")]
+    pub prefix_items: Vec<MetaSchemaRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<MetaAdditionalProperties>,
     #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
     pub enum_items: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub one_of: Vec<MetaSchemaRef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub any_of: Vec<MetaSchemaRef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub all_of: Vec<MetaSchemaRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<MetaDiscriminatorObject>,
     #[serde(skip_serializing_if = "is_false")]
     pub deprecated: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub read_only: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub write_only: bool,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub multiple_of: Option<f64>,
@@ -68,6 +91,9 @@ pub struct MetaSchema {
     pub min_items: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unique_items: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<MetaXml>,
 }
 
 fn serialize_properties<S: Serializer>(
@@ -81,7 +107,60 @@ fn serialize_properties<S: Serializer>(
     s.end()
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaAdditionalProperties {
+    Bool(bool),
+    Schema(Box<MetaSchemaRef>),
+}
+
+impl MetaAdditionalProperties {
+    pub fn schema(&self) -> Option<&MetaSchemaRef> {
+        match self {
+            MetaAdditionalProperties::Bool(_) => None,
+            MetaAdditionalProperties::Schema(schema) => Some(schema),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetaDiscriminatorObject {
+    pub property_name: &'static str,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_mapping"
+    )]
+    pub mapping: Vec<(String, String)>,
+}
+
+fn serialize_mapping<S: Serializer>(
+    mapping: &[(String, String)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_map(None)?;
+    for (name, reference) in mapping {
+        s.serialize_entry(name, reference)?;
+    }
+    s.end()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MetaXml {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<&'static str>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub attribute: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub wrapped: bool,
+}
+
 impl MetaSchema {
+    pub const ANY: Self = MetaSchema::new("");
+
     pub const fn new(ty: &'static str) -> Self {
         Self {
             ty,
@@ -92,8 +171,16 @@ impl MetaSchema {
             required: vec![],
             properties: vec![],
             items: None,
+            prefix_items: vec![],
+            additional_properties: None,
             enum_items: vec![],
+            one_of: vec![],
+            any_of: vec![],
+            all_of: vec![],
+            discriminator: None,
             deprecated: false,
+            read_only: false,
+            write_only: false,
             multiple_of: None,
             maximum: None,
             exclusive_maximum: None,
@@ -105,6 +192,7 @@ impl MetaSchema {
             max_items: None,
             min_items: None,
             unique_items: None,
+            xml: None,
         }
     }
 }
@@ -154,6 +242,15 @@ pub enum MetaParamIn {
     Cookie,
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MetaParamStyle {
+    Form,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
 #[derive(Debug, PartialEq, Serialize)]
 pub struct MetaOperationParam {
     pub name: &'static str,
@@ -164,6 +261,10 @@ pub struct MetaOperationParam {
     pub description: Option<&'static str>,
     pub required: bool,
     pub deprecated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<MetaParamStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
 }
 
 #[derive(Debug, PartialEq, Serialize)]
@@ -262,6 +363,66 @@ pub struct MetaOperation {
     pub security: Vec<HashMap<&'static str, Vec<&'static str>>>,
 }
 
+impl MetaOperation {
+    /// Creates the security requirements of an operation. All the security
+    /// schemes are required, and each of them may have several alternative
+    /// names, so every combination of the names is a separate requirement.
+    /// An optional scheme may also be omitted, so the operation allows
+    /// anonymous access if all of them are optional.
+    ///
+    /// The scopes are only listed for the `oauth2` and `openIdConnect`
+    /// schemes, the list must be empty for the other ones.
+    #[doc(hidden)]
+    pub fn security_requirements(
+        schemes: Vec<(
+            fn(&mut Registry),
+            Vec<&'static str>,
+            Vec<&'static str>,
+            bool,
+        )>,
+    ) -> Vec<HashMap<&'static str, Vec<&'static str>>> {
+        if schemes.is_empty() {
+            return Vec::new();
+        }
+
+        let mut registry = Registry::new();
+        for (register, _, _, _) in &schemes {
+            register(&mut registry);
+        }
+        let has_scopes = |name: &str| {
+            registry
+                .security_schemes
+                .get(name)
+                .map(|scheme| matches!(scheme.ty, "oauth2" | "openIdConnect"))
+                .unwrap_or_default()
+        };
+
+        let mut requirements = vec![HashMap::new()];
+        for (_, names, scopes, optional) in &schemes {
+            requirements = requirements
+                .iter()
+                .flat_map(|requirement| {
+                    names
+                        .iter()
+                        .map(|name| {
+                            let mut requirement = requirement.clone();
+                            let scopes = if has_scopes(name) {
+                                scopes.clone()
+                            } else {
+                                Vec::new()
+                            };
+                            requirement.insert(*name, scopes);
+                            requirement
+                        })
+                        .chain(optional.then(|| requirement.clone()))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+        }
+        requirements
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct MetaPath {
     pub path: &'static str,
@@ -409,6 +570,29 @@ impl Registry {
         }
     }
 
+    /// Collects the properties of an object schema, including the properties
+    /// of the objects it extends, used to flatten it into another object.
+    #[doc(hidden)]
+    pub fn object_properties(
+        &self,
+        schema_ref: &MetaSchemaRef,
+        properties: &mut Vec<(&'static str, MetaSchemaRef)>,
+        required: &mut Vec<&'static str>,
+    ) {
+        let schema = match schema_ref {
+            MetaSchemaRef::Inline(schema) => schema,
+            MetaSchemaRef::Reference(name) => match self.schemas.get(name) {
+                Some(schema) => schema,
+                None => return,
+            },
+        };
+        for schema_ref in &schema.all_of {
+            self.object_properties(schema_ref, properties, required);
+        }
+        properties.extend(schema.properties.iter().cloned());
+        required.extend(schema.required.iter().copied());
+    }
+
     pub fn create_tag(&mut self, tag: MetaTag) {
         self.tags.insert(tag);
     }
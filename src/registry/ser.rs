@@ -1,17 +1,19 @@
 use std::collections::{BTreeMap, HashMap};
 
 use serde::{
-    ser::{SerializeMap, SerializeStruct},
+    ser::{Error as _, SerializeMap, SerializeStruct},
     Serialize, Serializer,
 };
+use serde_json::{json, Map, Value};
 
-use crate::registry::{
-    MetaApi, MetaInfo, MetaPath, MetaResponses, MetaSchema, MetaSchemaRef, MetaSecurityScheme,
-    MetaServer, Registry,
+use crate::{
+    registry::{
+        MetaAdditionalProperties, MetaApi, MetaInfo, MetaPath, MetaResponses, MetaSchema,
+        MetaSchemaRef, MetaSecurityScheme, MetaServer, Registry,
+    },
+    OpenApiVersion,
 };
 
-const OPENAPI_VERSION: &str = "3.0.0";
-
 impl<'a> Serialize for MetaSchemaRef {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
@@ -25,6 +27,15 @@ impl<'a> Serialize for MetaSchemaRef {
     }
 }
 
+impl Serialize for MetaAdditionalProperties {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MetaAdditionalProperties::Bool(value) => value.serialize(serializer),
+            MetaAdditionalProperties::Schema(schema) => schema.serialize(serializer),
+        }
+    }
+}
+
 struct PathMap<'a>(&'a [MetaApi]);
 
 impl<'a> Serialize for PathMap<'a> {
@@ -39,6 +50,20 @@ impl<'a> Serialize for PathMap<'a> {
     }
 }
 
+struct WebhookMap<'a>(&'a [MetaApi]);
+
+impl<'a> Serialize for WebhookMap<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_map(None)?;
+        for api in self.0 {
+            for path in &api.paths {
+                s.serialize_entry(path.path.trim_start_matches('/'), path)?;
+            }
+        }
+        s.end()
+    }
+}
+
 impl Serialize for MetaPath {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let mut s = serializer.serialize_map(None)?;
@@ -65,14 +90,16 @@ impl Serialize for MetaResponses {
 }
 
 pub(crate) struct Document<'a> {
+    pub(crate) version: OpenApiVersion,
     pub(crate) info: Option<&'a MetaInfo>,
     pub(crate) servers: &'a [MetaServer],
     pub(crate) apis: &'a [MetaApi],
+    pub(crate) webhooks: &'a [MetaApi],
     pub(crate) registry: &'a Registry,
 }
 
-impl<'a> Serialize for Document<'a> {
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+impl<'a> Document<'a> {
+    fn serialize_v3_0<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         #[derive(Serialize)]
         struct Components<'a> {
             schemas: &'a HashMap<&'static str, MetaSchema>,
@@ -80,13 +107,16 @@ impl<'a> Serialize for Document<'a> {
             security_schemes: &'a BTreeMap<&'static str, MetaSecurityScheme>,
         }
 
-        let mut s = serializer.serialize_struct("OpenAPI", 6)?;
+        let mut s = serializer.serialize_struct("OpenAPI", 7)?;
 
-        s.serialize_field("openapi", OPENAPI_VERSION)?;
+        s.serialize_field("openapi", self.version.as_str())?;
         s.serialize_field("info", &self.info)?;
         s.serialize_field("servers", self.servers)?;
         s.serialize_field("tags", &self.registry.tags)?;
         s.serialize_field("paths", &PathMap(self.apis))?;
+        if self.version == OpenApiVersion::V3_1_0 && !self.webhooks.is_empty() {
+            s.serialize_field("webhooks", &WebhookMap(self.webhooks))?;
+        }
         s.serialize_field(
             "components",
             &Components {
@@ -98,3 +128,193 @@ impl<'a> Serialize for Document<'a> {
         s.end()
     }
 }
+
+impl<'a> Serialize for Document<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct V3_0<'a, 'b>(&'a Document<'b>);
+
+        impl<'a, 'b> Serialize for V3_0<'a, 'b> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.serialize_v3_0(serializer)
+            }
+        }
+
+        let mut doc = serde_json::to_value(V3_0(self)).map_err(S::Error::custom)?;
+        convert_document(&mut doc, self.version);
+        doc.serialize(serializer)
+    }
+}
+
+/// Rewrites all schemas of a document for the given version.
+///
+/// A 3.0 document only drops the extensions that carry the 3.1 keywords, a
+/// 3.1 document uses the JSON Schema 2020-12 semantics.
+fn convert_document(doc: &mut Value, version: OpenApiVersion) {
+    if let Some(schemas) = doc
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+    {
+        for schema in schemas.values_mut() {
+            convert_schema(schema, version);
+        }
+    }
+
+    for key in ["paths", "webhooks"] {
+        if let Some(paths) = doc.get_mut(key).and_then(Value::as_object_mut) {
+            for operation in paths.values_mut().filter_map(Value::as_object_mut) {
+                for operation in operation.values_mut() {
+                    convert_operation(operation, version);
+                }
+            }
+        }
+    }
+}
+
+fn convert_operation(operation: &mut Value, version: OpenApiVersion) {
+    if let Some(params) = operation
+        .get_mut("parameters")
+        .and_then(Value::as_array_mut)
+    {
+        for param in params {
+            let required = param.get("required") == Some(&Value::Bool(true));
+            if let Some(schema) = param.get_mut("schema") {
+                convert_schema(schema, version);
+                if !required && version == OpenApiVersion::V3_1_0 {
+                    make_nullable(schema);
+                }
+            }
+        }
+    }
+
+    if let Some(content) = operation.pointer_mut("/requestBody/content") {
+        convert_content(content, version);
+    }
+
+    if let Some(responses) = operation
+        .get_mut("responses")
+        .and_then(Value::as_object_mut)
+    {
+        for response in responses.values_mut() {
+            if let Some(content) = response.get_mut("content") {
+                convert_content(content, version);
+            }
+            if let Some(headers) = response.get_mut("headers").and_then(Value::as_object_mut) {
+                for header in headers.values_mut() {
+                    if let Some(schema) = header.get_mut("schema") {
+                        convert_schema(schema, version);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn convert_content(content: &mut Value, version: OpenApiVersion) {
+    if let Some(content) = content.as_object_mut() {
+        for media_type in content.values_mut() {
+            if let Some(schema) = media_type.get_mut("schema") {
+                convert_schema(schema, version);
+            }
+        }
+    }
+}
+
+fn convert_schema(schema: &mut Value, version: OpenApiVersion) {
+    let schema = match schema.as_object_mut() {
+        Some(schema) => schema,
+        None => return,
+    };
+
+    let prefix_items = schema.remove("x-prefixItems");
+
+    if version == OpenApiVersion::V3_1_0 {
+        convert_exclusive_bound(schema, "exclusiveMaximum", "maximum");
+        convert_exclusive_bound(schema, "exclusiveMinimum", "minimum");
+
+        // The length of a tuple is bounded by `minItems` and `maxItems`, so the
+        // `items` schema that documents its items in 3.0 is not needed.
+        if let Some(mut prefix_items) = prefix_items {
+            schema.remove("items");
+            if let Some(prefix_items) = prefix_items.as_array_mut() {
+                for item in prefix_items.iter_mut() {
+                    convert_schema(item, version);
+                }
+            }
+            schema.insert("prefixItems".to_string(), prefix_items);
+        }
+    }
+
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+        for (name, property) in properties.iter_mut() {
+            convert_schema(property, version);
+            if version == OpenApiVersion::V3_1_0
+                && !required
+                    .iter()
+                    .any(|required| required.as_str() == Some(name))
+            {
+                make_nullable(property);
+            }
+        }
+    }
+
+    if let Some(items) = schema.get_mut("items") {
+        convert_schema(items, version);
+    }
+
+    if let Some(additional_properties) = schema.get_mut("additionalProperties") {
+        convert_schema(additional_properties, version);
+    }
+
+    for key in ["oneOf", "anyOf", "allOf"] {
+        if let Some(schemas) = schema.get_mut(key).and_then(Value::as_array_mut) {
+            for schema in schemas {
+                convert_schema(schema, version);
+            }
+        }
+    }
+}
+
+fn convert_exclusive_bound(schema: &mut Map<String, Value>, exclusive: &str, bound: &str) {
+    match schema.remove(exclusive) {
+        Some(Value::Bool(true)) => {
+            if let Some(value) = schema.remove(bound) {
+                schema.insert(exclusive.to_string(), value);
+            }
+        }
+        Some(Value::Bool(false)) | None => {}
+        Some(value) => {
+            schema.insert(exclusive.to_string(), value);
+        }
+    }
+}
+
+/// Optional fields and parameters accept `null`, which OpenAPI 3.1 expresses
+/// by adding `"null"` to the types of the schema, or by allowing a `null`
+/// type next to a schema without a type, such as a reference.
+fn make_nullable(schema: &mut Value) {
+    let null = Value::String("null".to_string());
+    let map = match schema.as_object_mut() {
+        Some(map) if !map.is_empty() => map,
+        _ => return,
+    };
+
+    match map.remove("type") {
+        Some(Value::Array(mut types)) => {
+            if !types.contains(&null) {
+                types.push(null);
+            }
+            map.insert("type".to_string(), Value::Array(types));
+        }
+        Some(ty) => {
+            map.insert("type".to_string(), Value::Array(vec![ty, null]));
+        }
+        None => {
+            *schema = json!({ "anyOf": [schema.take(), { "type": "null" }] });
+        }
+    }
+}
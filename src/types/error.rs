@@ -3,6 +3,7 @@ use std::{fmt::Display, marker::PhantomData};
 use serde_json::Value;
 
 use super::Type;
+use crate::Violation;
 
 /// An error parsing an schema.
 ///
@@ -11,6 +12,7 @@ use super::Type;
 #[derive(Debug)]
 pub struct ParseError<T> {
     message: String,
+    violations: Vec<Violation>,
     phantom: PhantomData<T>,
 }
 
@@ -24,6 +26,7 @@ impl<T: Type> ParseError<T> {
     fn new(message: String) -> Self {
         Self {
             message,
+            violations: Vec::new(),
             phantom: PhantomData,
         }
     }
@@ -65,17 +68,59 @@ impl<T: Type> ParseError<T> {
         Self::new(format!(r#"failed to parse "{}": {}"#, T::NAME, msg))
     }
 
+    /// Attach the violations found while parsing the value.
+    #[must_use]
+    pub fn with_violations(self, violations: Vec<Violation>) -> Self {
+        Self { violations, ..self }
+    }
+
+    /// Merge multiple errors of the same value into one, keeping all the
+    /// violations.
+    #[must_use]
+    pub fn merge(errors: Vec<Self>) -> Self {
+        let mut messages = Vec::with_capacity(errors.len());
+        let mut violations = Vec::new();
+        for err in errors {
+            messages.push(err.message);
+            violations.extend(err.violations);
+        }
+        Self::new(messages.join("; ")).with_violations(violations)
+    }
+
     /// Propagate the error message to a different type.
     pub fn propagate<U: Type>(self) -> ParseError<U> {
-        if T::NAME != U::NAME {
-            ParseError::new(format!(
-                r#"{} (occurred while parsing "{}")"#,
-                self.message,
-                U::NAME
-            ))
+        let message = if T::NAME != U::NAME {
+            format!(r#"{} (occurred while parsing "{}")"#, self.message, U::NAME)
+        } else {
+            self.message
+        };
+        ParseError::new(message).with_violations(self.violations)
+    }
+
+    /// Propagate the error of a field or an item to a different type, and
+    /// prefix the JSON pointer of the violations with `field`.
+    pub fn propagate_field<U: Type>(mut self, field: &str) -> ParseError<U> {
+        let token = format!("/{}", field.replace('~', "~0").replace('/', "~1"));
+        if self.violations.is_empty() {
+            self.violations.push(Violation {
+                pointer: token,
+                parameter: None,
+                validator: None,
+                expected: None,
+                actual: None,
+                message: self.message.clone(),
+            });
         } else {
-            ParseError::new(self.message)
+            for violation in &mut self.violations {
+                violation.pointer.insert_str(0, &token);
+            }
         }
+        self.propagate()
+    }
+
+    /// Returns the violations found while parsing the value.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
     }
 
     /// Consume this error and convert it into a message.
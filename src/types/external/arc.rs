@@ -0,0 +1,3 @@
+use std::sync::Arc;
+
+impl_smart_pointer_type!(Arc);
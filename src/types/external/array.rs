@@ -0,0 +1,66 @@
+use std::convert::TryFrom;
+
+use serde_json::Value;
+
+use crate::{
+    registry::{MetaSchema, MetaSchemaRef, Registry},
+    types::{ParseError, ParseFromJSON, ParseResult, ToJSON, Type, TypeName},
+};
+
+impl<T: Type, const LEN: usize> Type for [T; LEN] {
+    const NAME: TypeName = TypeName::Array(&T::NAME);
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(MetaSchema {
+            items: Some(Box::new(T::schema_ref())),
+            max_items: Some(LEN),
+            min_items: Some(LEN),
+            ..MetaSchema::new("array")
+        })
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
+    impl_value_type!();
+}
+
+impl<T: ParseFromJSON, const LEN: usize> ParseFromJSON for [T; LEN] {
+    fn parse_from_json(value: Value) -> ParseResult<Self> {
+        match value {
+            Value::Array(values) => {
+                if values.len() != LEN {
+                    return Err(ParseError::custom(format!(
+                        "the length of the list must be `{}`.",
+                        LEN
+                    )));
+                }
+
+                let mut res = Vec::with_capacity(LEN);
+                let mut errors = Vec::new();
+                for (idx, value) in values.into_iter().enumerate() {
+                    match T::parse_from_json(value) {
+                        Ok(value) => res.push(value),
+                        Err(err) => errors.push(err.propagate_field(&idx.to_string())),
+                    }
+                }
+                if !errors.is_empty() {
+                    return Err(ParseError::merge(errors));
+                }
+                Ok(<[T; LEN]>::try_from(res).ok().unwrap())
+            }
+            _ => Err(ParseError::expected_type(value)),
+        }
+    }
+}
+
+impl<T: ToJSON, const LEN: usize> ToJSON for [T; LEN] {
+    fn to_json(&self) -> Value {
+        let mut values = Vec::with_capacity(LEN);
+        for item in self {
+            values.push(item.to_json());
+        }
+        Value::Array(values)
+    }
+}
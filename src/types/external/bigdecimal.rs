@@ -0,0 +1,9 @@
+use bigdecimal::BigDecimal;
+
+impl_string_format_type!(
+    BigDecimal,
+    "decimal",
+    str::parse,
+    BigDecimal::to_string,
+    from_number
+);
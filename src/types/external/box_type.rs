@@ -0,0 +1 @@
+impl_smart_pointer_type!(Box);
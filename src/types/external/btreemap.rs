@@ -0,0 +1,3 @@
+use std::collections::BTreeMap;
+
+impl_map_type!(BTreeMap, |_| BTreeMap::new());
@@ -0,0 +1,3 @@
+use std::collections::BTreeSet;
+
+impl_set_type!(BTreeSet, [Ord], |_| BTreeSet::new());
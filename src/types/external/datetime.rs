@@ -1,45 +1,30 @@
-use chrono::{DateTime, FixedOffset};
-use serde_json::Value;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, Utc};
 
-use crate::{
-    registry::MetaSchemaRef,
-    types::{ParseError, ParseFromJSON, ParseFromParameter, ParseResult, ToJSON, Type, TypeName},
-};
+const NAIVE_DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+const NAIVE_DATE_FORMAT: &str = "%Y-%m-%d";
 
-impl Type for DateTime<FixedOffset> {
-    const NAME: TypeName = TypeName::Normal {
-        ty: "string",
-        format: Some("data-time"),
-    };
-
-    fn schema_ref() -> MetaSchemaRef {
-        MetaSchemaRef::Inline(Self::NAME.into())
-    }
-
-    impl_value_type!();
-}
-
-impl ParseFromJSON for DateTime<FixedOffset> {
-    fn parse_from_json(value: Value) -> ParseResult<Self> {
-        if let Value::String(value) = value {
-            Ok(value.parse()?)
-        } else {
-            Err(ParseError::expected_type(value))
-        }
-    }
-}
-
-impl ParseFromParameter for DateTime<FixedOffset> {
-    fn parse_from_parameter(value: Option<&str>) -> ParseResult<Self> {
-        match value {
-            Some(value) => Ok(value.parse()?),
-            None => Err(ParseError::expected_input()),
-        }
-    }
-}
-
-impl ToJSON for DateTime<FixedOffset> {
-    fn to_json(&self) -> Value {
-        Value::String(self.to_rfc3339())
-    }
-}
+impl_string_format_type!(
+    DateTime<FixedOffset>,
+    "date-time",
+    str::parse,
+    DateTime::to_rfc3339
+);
+impl_string_format_type!(DateTime<Utc>, "date-time", str::parse, DateTime::to_rfc3339);
+impl_string_format_type!(
+    DateTime<Local>,
+    "date-time",
+    str::parse,
+    DateTime::to_rfc3339
+);
+impl_string_format_type!(
+    NaiveDateTime,
+    "naive-date-time",
+    |value| NaiveDateTime::parse_from_str(value, NAIVE_DATE_TIME_FORMAT),
+    |value: &NaiveDateTime| value.format(NAIVE_DATE_TIME_FORMAT).to_string()
+);
+impl_string_format_type!(
+    NaiveDate,
+    "date",
+    |value| NaiveDate::parse_from_str(value, NAIVE_DATE_FORMAT),
+    |value: &NaiveDate| value.format(NAIVE_DATE_FORMAT).to_string()
+);
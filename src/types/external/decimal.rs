@@ -0,0 +1,9 @@
+use rust_decimal::Decimal;
+
+impl_string_format_type!(
+    Decimal,
+    "decimal",
+    str::parse,
+    Decimal::to_string,
+    from_number
+);
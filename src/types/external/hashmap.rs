@@ -0,0 +1,3 @@
+use std::collections::HashMap;
+
+impl_map_type!(HashMap, HashMap::with_capacity);
@@ -0,0 +1,3 @@
+use std::{collections::HashSet, hash::Hash};
+
+impl_set_type!(HashSet, [Eq + Hash], HashSet::with_capacity);
@@ -26,9 +26,11 @@ macro_rules! impl_type_for_integers {
                 if let Value::Number(n) = value {
                     let n = n
                         .as_i64()
+                        .map(i128::from)
+                        .or_else(|| n.as_u64().map(i128::from))
                         .ok_or_else(|| ParseError::from("invalid integer"))?;
 
-                    if n < Self::MIN as i64 || n > Self::MAX as i64 {
+                    if n < Self::MIN as i128 || n > Self::MAX as i128 {
                         return Err(ParseError::from(format!(
                             "Only integers from {} to {} are accepted.",
                             Self::MIN,
@@ -67,8 +69,19 @@ impl_type_for_integers!(
     (i16, "int16"),
     (i32, "int32"),
     (i64, "int64"),
+    (isize, "int64"),
     (u8, "uint8"),
     (u16, "uint16"),
     (u32, "uint32"),
-    (u64, "uint64")
+    (u64, "uint64"),
+    (usize, "uint64")
+);
+
+impl_string_format_type!(i128, "int128", str::parse, ToString::to_string, from_number);
+impl_string_format_type!(
+    u128,
+    "uint128",
+    str::parse,
+    ToString::to_string,
+    from_number
 );
@@ -0,0 +1,52 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use serde_json::Value;
+
+use crate::{
+    registry::{MetaSchema, MetaSchemaRef},
+    types::{ParseError, ParseFromJSON, ParseFromParameter, ParseResult, ToJSON, Type, TypeName},
+};
+
+impl_string_format_type!(Ipv4Addr, "ipv4", str::parse, Ipv4Addr::to_string);
+impl_string_format_type!(Ipv6Addr, "ipv6", str::parse, Ipv6Addr::to_string);
+
+impl Type for IpAddr {
+    const NAME: TypeName = TypeName::Normal {
+        ty: "string",
+        format: None,
+    };
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(MetaSchema {
+            one_of: vec![Ipv4Addr::schema_ref(), Ipv6Addr::schema_ref()],
+            ..MetaSchema::new("string")
+        })
+    }
+
+    impl_value_type!();
+}
+
+impl ParseFromJSON for IpAddr {
+    fn parse_from_json(value: Value) -> ParseResult<Self> {
+        if let Value::String(value) = value {
+            Ok(value.parse()?)
+        } else {
+            Err(ParseError::expected_type(value))
+        }
+    }
+}
+
+impl ParseFromParameter for IpAddr {
+    fn parse_from_parameter(value: Option<&str>) -> ParseResult<Self> {
+        match value {
+            Some(value) => Ok(value.parse()?),
+            None => Err(ParseError::expected_input()),
+        }
+    }
+}
+
+impl ToJSON for IpAddr {
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -1,7 +1,28 @@
+mod any;
+mod arc;
+mod array;
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal;
+mod boolean;
+mod box_type;
+mod btreemap;
+mod btreeset;
 #[cfg(feature = "chrono")]
 mod datetime;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
 mod floats;
+mod hashmap;
+mod hashset;
 mod integers;
+mod ip;
 mod optional;
 mod string;
+#[cfg(feature = "time")]
+mod time;
+mod tuple;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "uuid")]
+mod uuid;
 mod vec;
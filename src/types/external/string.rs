@@ -2,7 +2,7 @@ use poem::web::Field;
 use serde_json::Value;
 
 use crate::{
-    registry::MetaSchemaRef,
+    registry::{MetaSchema, MetaSchemaRef},
     types::{
         ParseError, ParseFromJSON, ParseFromMultipartField, ParseFromParameter, ParseResult,
         ToJSON, Type, TypeName,
@@ -75,3 +75,51 @@ impl<'a> ToJSON for &'a str {
         Value::String(self.to_string())
     }
 }
+
+impl Type for char {
+    const NAME: TypeName = TypeName::Normal {
+        ty: "string",
+        format: None,
+    };
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(MetaSchema {
+            max_length: Some(1),
+            min_length: Some(1),
+            ..MetaSchema::new("string")
+        })
+    }
+
+    impl_value_type!();
+}
+
+impl ParseFromJSON for char {
+    fn parse_from_json(value: Value) -> ParseResult<Self> {
+        if let Value::String(value) = value {
+            Self::parse_from_parameter(Some(&value))
+        } else {
+            Err(ParseError::expected_type(value))
+        }
+    }
+}
+
+impl ParseFromParameter for char {
+    fn parse_from_parameter(value: Option<&str>) -> ParseResult<Self> {
+        match value {
+            Some(value) => {
+                let mut chars = value.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(ParseError::custom("expected a single character.")),
+                }
+            }
+            None => Err(ParseError::expected_input()),
+        }
+    }
+}
+
+impl ToJSON for char {
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
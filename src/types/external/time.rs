@@ -0,0 +1,35 @@
+use time::{
+    format_description::{well_known::Rfc3339, FormatItem},
+    macros::format_description,
+    Date, OffsetDateTime, PrimitiveDateTime,
+};
+
+const PRIMITIVE_DATE_TIME_FORMAT: &[FormatItem<'static>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]");
+const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
+
+// Formatting only fails for the values that can't be represented in the
+// format, such as the negative years in RFC 3339, they are written with the
+// `Display` implementation instead of an empty string.
+impl_string_format_type!(
+    OffsetDateTime,
+    "date-time",
+    |value| OffsetDateTime::parse(value, &Rfc3339),
+    |value: &OffsetDateTime| value.format(&Rfc3339).unwrap_or_else(|_| value.to_string())
+);
+impl_string_format_type!(
+    PrimitiveDateTime,
+    "naive-date-time",
+    |value| PrimitiveDateTime::parse(value, PRIMITIVE_DATE_TIME_FORMAT),
+    |value: &PrimitiveDateTime| value
+        .format(PRIMITIVE_DATE_TIME_FORMAT)
+        .unwrap_or_else(|_| value.to_string())
+);
+impl_string_format_type!(
+    Date,
+    "date",
+    |value| Date::parse(value, DATE_FORMAT),
+    |value: &Date| value
+        .format(DATE_FORMAT)
+        .unwrap_or_else(|_| value.to_string())
+);
@@ -0,0 +1,86 @@
+use serde_json::Value;
+
+use crate::{
+    registry::{MetaSchema, MetaSchemaRef, Registry},
+    types::{ParseError, ParseFromJSON, ParseResult, ToJSON, Type, TypeName},
+};
+
+macro_rules! impl_tuple_type {
+    ($($idx:tt $ty:ident),+) => {
+        impl<$($ty: Type),+> Type for ($($ty,)+) {
+            const NAME: TypeName = TypeName::Normal {
+                ty: "array",
+                format: None,
+            };
+
+            fn schema_ref() -> MetaSchemaRef {
+                let len = [$($idx),+].len();
+                let prefix_items = vec![$($ty::schema_ref()),+];
+                MetaSchemaRef::Inline(MetaSchema {
+                    items: Some(Box::new(MetaSchemaRef::Inline(MetaSchema {
+                        any_of: prefix_items.clone(),
+                        ..MetaSchema::ANY
+                    }))),
+                    prefix_items,
+                    max_items: Some(len),
+                    min_items: Some(len),
+                    ..MetaSchema::new("array")
+                })
+            }
+
+            fn register(registry: &mut Registry) {
+                $($ty::register(registry);)+
+            }
+
+            impl_value_type!();
+        }
+
+        impl<$($ty: ParseFromJSON),+> ParseFromJSON for ($($ty,)+) {
+            fn parse_from_json(value: Value) -> ParseResult<Self> {
+                match value {
+                    Value::Array(values) => {
+                        let len = [$($idx),+].len();
+                        if values.len() != len {
+                            return Err(ParseError::custom(format!(
+                                "the length of the list must be `{}`.",
+                                len
+                            )));
+                        }
+
+                        let mut values = values.into_iter();
+                        let mut errors = Vec::new();
+                        let res = ($(
+                            match $ty::parse_from_json(values.next().unwrap()) {
+                                Ok(value) => Some(value),
+                                Err(err) => {
+                                    errors.push(err.propagate_field(stringify!($idx)));
+                                    None
+                                }
+                            },
+                        )+);
+                        if !errors.is_empty() {
+                            return Err(ParseError::merge(errors));
+                        }
+                        Ok(($(res.$idx.unwrap(),)+))
+                    }
+                    _ => Err(ParseError::expected_type(value)),
+                }
+            }
+        }
+
+        impl<$($ty: ToJSON),+> ToJSON for ($($ty,)+) {
+            fn to_json(&self) -> Value {
+                Value::Array(vec![$(self.$idx.to_json()),+])
+            }
+        }
+    };
+}
+
+impl_tuple_type!(0 T0);
+impl_tuple_type!(0 T0, 1 T1);
+impl_tuple_type!(0 T0, 1 T1, 2 T2);
+impl_tuple_type!(0 T0, 1 T1, 2 T2, 3 T3);
+impl_tuple_type!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4);
+impl_tuple_type!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5);
+impl_tuple_type!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6);
+impl_tuple_type!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7);
@@ -0,0 +1,3 @@
+use url::Url;
+
+impl_string_format_type!(Url, "uri", str::parse, Url::to_string);
@@ -0,0 +1,3 @@
+use uuid::Uuid;
+
+impl_string_format_type!(Uuid, "uuid", str::parse, Uuid::to_string);
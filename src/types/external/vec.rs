@@ -1,9 +1,10 @@
 use crate::{
     poem::web::Field as PoemField,
-    registry::{MetaSchema, MetaSchemaRef},
+    registry::{MetaSchema, MetaSchemaRef, Registry},
     serde_json::Value,
     types::{
-        ParseError, ParseFromJSON, ParseFromMultipartField, ParseResult, ToJSON, Type, TypeName,
+        ParseError, ParseFromJSON, ParseFromMultipartField, ParseFromParameter, ParseResult,
+        ToJSON, Type, TypeName,
     },
 };
 
@@ -17,6 +18,10 @@ impl<T: Type> Type for Vec<T> {
         })
     }
 
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+
     impl_value_type!();
 }
 
@@ -25,8 +30,15 @@ impl<T: ParseFromJSON> ParseFromJSON for Vec<T> {
         match value {
             Value::Array(values) => {
                 let mut res = Vec::with_capacity(values.len());
-                for value in values {
-                    res.push(T::parse_from_json(value).map_err(ParseError::propagate)?);
+                let mut errors = Vec::new();
+                for (idx, value) in values.into_iter().enumerate() {
+                    match T::parse_from_json(value) {
+                        Ok(value) => res.push(value),
+                        Err(err) => errors.push(err.propagate_field(&idx.to_string())),
+                    }
+                }
+                if !errors.is_empty() {
+                    return Err(ParseError::merge(errors));
                 }
                 Ok(res)
             }
@@ -35,6 +47,34 @@ impl<T: ParseFromJSON> ParseFromJSON for Vec<T> {
     }
 }
 
+impl<T: ParseFromParameter> ParseFromParameter for Vec<T> {
+    fn parse_from_parameter(value: Option<&str>) -> ParseResult<Self> {
+        match value {
+            Some(value) => Self::parse_from_parameters(&value.split(',').collect::<Vec<_>>()),
+            None => Err(ParseError::expected_input()),
+        }
+    }
+
+    fn parse_from_parameters(values: &[&str]) -> ParseResult<Self> {
+        if values.is_empty() {
+            return Err(ParseError::expected_input());
+        }
+
+        let mut res = Vec::with_capacity(values.len());
+        let mut errors = Vec::new();
+        for (idx, value) in values.iter().enumerate() {
+            match T::parse_from_parameter(Some(value)) {
+                Ok(value) => res.push(value),
+                Err(err) => errors.push(err.propagate_field(&idx.to_string())),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(ParseError::merge(errors));
+        }
+        Ok(res)
+    }
+}
+
 #[poem::async_trait]
 impl<T: ParseFromMultipartField> ParseFromMultipartField for Vec<T> {
     async fn parse_from_multipart(field: Option<PoemField>) -> ParseResult<Self> {
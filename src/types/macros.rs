@@ -6,3 +6,298 @@ macro_rules! impl_value_type {
         }
     };
 }
+
+macro_rules! impl_string_format_type {
+    ($ty:ty, $format:literal, $parse:expr, $to_string:expr) => {
+        impl_string_format_type!(@impl $ty, $format, $parse, $to_string, false);
+    };
+    // Also accepts JSON numbers, such as decimals.
+    ($ty:ty, $format:literal, $parse:expr, $to_string:expr, from_number) => {
+        impl_string_format_type!(@impl $ty, $format, $parse, $to_string, true);
+    };
+    (@impl $ty:ty, $format:literal, $parse:expr, $to_string:expr, $from_number:literal) => {
+        impl $crate::types::Type for $ty {
+            const NAME: $crate::types::TypeName = $crate::types::TypeName::Normal {
+                ty: "string",
+                format: Some($format),
+            };
+
+            fn schema_ref() -> $crate::registry::MetaSchemaRef {
+                $crate::registry::MetaSchemaRef::Inline(Self::NAME.into())
+            }
+
+            impl_value_type!();
+        }
+
+        impl $crate::types::ParseFromJSON for $ty {
+            fn parse_from_json(value: ::serde_json::Value) -> $crate::types::ParseResult<Self> {
+                match value {
+                    ::serde_json::Value::String(value) => {
+                        ($parse)(value.as_str()).map_err($crate::types::ParseError::custom)
+                    }
+                    ::serde_json::Value::Number(value) if $from_number => ($parse)(
+                        value.to_string().as_str(),
+                    )
+                    .map_err($crate::types::ParseError::custom),
+                    value => Err($crate::types::ParseError::expected_type(value)),
+                }
+            }
+        }
+
+        impl $crate::types::ParseFromParameter for $ty {
+            fn parse_from_parameter(value: Option<&str>) -> $crate::types::ParseResult<Self> {
+                match value {
+                    Some(value) => ($parse)(value).map_err($crate::types::ParseError::custom),
+                    None => Err($crate::types::ParseError::expected_input()),
+                }
+            }
+        }
+
+        impl $crate::types::ToJSON for $ty {
+            fn to_json(&self) -> ::serde_json::Value {
+                ::serde_json::Value::String(($to_string)(self))
+            }
+        }
+    };
+}
+
+macro_rules! impl_map_type {
+    ($ty:ident, $new:expr) => {
+        impl<T: $crate::types::Type> $crate::types::Type for $ty<String, T> {
+            const NAME: $crate::types::TypeName = $crate::types::TypeName::Normal {
+                ty: "object",
+                format: None,
+            };
+
+            fn schema_ref() -> $crate::registry::MetaSchemaRef {
+                $crate::registry::MetaSchemaRef::Inline($crate::registry::MetaSchema {
+                    additional_properties: Some(
+                        $crate::registry::MetaAdditionalProperties::Schema(Box::new(
+                            T::schema_ref(),
+                        )),
+                    ),
+                    ..$crate::registry::MetaSchema::new("object")
+                })
+            }
+
+            fn register(registry: &mut $crate::registry::Registry) {
+                T::register(registry);
+            }
+
+            impl_value_type!();
+        }
+
+        impl<T: $crate::types::ParseFromJSON> $crate::types::ParseFromJSON for $ty<String, T> {
+            fn parse_from_json(value: ::serde_json::Value) -> $crate::types::ParseResult<Self> {
+                match value {
+                    ::serde_json::Value::Object(values) => {
+                        let mut res: Self = ($new)(values.len());
+                        let mut errors = Vec::new();
+                        for (key, value) in values {
+                            match T::parse_from_json(value) {
+                                Ok(value) => {
+                                    res.insert(key, value);
+                                }
+                                Err(err) => errors.push(err.propagate_field(&key)),
+                            }
+                        }
+                        if !errors.is_empty() {
+                            return Err($crate::types::ParseError::merge(errors));
+                        }
+                        Ok(res)
+                    }
+                    _ => Err($crate::types::ParseError::expected_type(value)),
+                }
+            }
+        }
+
+        impl<T: $crate::types::ParseFromParameter> $crate::types::ParseFromParameter
+            for $ty<String, T>
+        {
+            fn parse_from_parameter(value: Option<&str>) -> $crate::types::ParseResult<Self> {
+                match value {
+                    Some(value) => {
+                        Self::parse_from_parameters(&value.split(',').collect::<Vec<_>>())
+                    }
+                    None => Err($crate::types::ParseError::expected_input()),
+                }
+            }
+
+            fn parse_from_parameters(values: &[&str]) -> $crate::types::ParseResult<Self> {
+                if values.is_empty() {
+                    return Err($crate::types::ParseError::expected_input());
+                }
+
+                let pairs = values.chunks_exact(2);
+                if !pairs.remainder().is_empty() {
+                    return Err($crate::types::ParseError::custom(
+                        "expected a list of keys and values.",
+                    ));
+                }
+
+                let mut res: Self = ($new)(values.len() / 2);
+                for pair in pairs {
+                    res.insert(
+                        pair[0].to_string(),
+                        T::parse_from_parameter(Some(pair[1]))
+                            .map_err($crate::types::ParseError::propagate)?,
+                    );
+                }
+                Ok(res)
+            }
+        }
+
+        impl<T: $crate::types::ToJSON> $crate::types::ToJSON for $ty<String, T> {
+            fn to_json(&self) -> ::serde_json::Value {
+                let mut values = ::serde_json::Map::new();
+                for (key, value) in self {
+                    values.insert(key.clone(), value.to_json());
+                }
+                ::serde_json::Value::Object(values)
+            }
+        }
+    };
+}
+
+macro_rules! impl_set_type {
+    ($ty:ident, [$($bound:tt)*], $new:expr) => {
+        impl<T: $crate::types::Type + $($bound)*> $crate::types::Type for $ty<T> {
+            const NAME: $crate::types::TypeName = $crate::types::TypeName::Array(&T::NAME);
+
+            fn schema_ref() -> $crate::registry::MetaSchemaRef {
+                $crate::registry::MetaSchemaRef::Inline($crate::registry::MetaSchema {
+                    items: Some(Box::new(T::schema_ref())),
+                    unique_items: Some(true),
+                    ..$crate::registry::MetaSchema::new("array")
+                })
+            }
+
+            fn register(registry: &mut $crate::registry::Registry) {
+                T::register(registry);
+            }
+
+            impl_value_type!();
+        }
+
+        impl<T: $crate::types::ParseFromJSON + $($bound)*> $crate::types::ParseFromJSON for $ty<T> {
+            fn parse_from_json(value: ::serde_json::Value) -> $crate::types::ParseResult<Self> {
+                match value {
+                    ::serde_json::Value::Array(values) => {
+                        let mut res: Self = ($new)(values.len());
+                        let mut errors = Vec::new();
+                        for (idx, value) in values.into_iter().enumerate() {
+                            match T::parse_from_json(value) {
+                                Ok(value) => {
+                                    if !res.insert(value) {
+                                        errors.push($crate::types::ParseError::custom(
+                                            "the items in the list must be unique.",
+                                        ));
+                                    }
+                                }
+                                Err(err) => errors.push(err.propagate_field(&idx.to_string())),
+                            }
+                        }
+                        if !errors.is_empty() {
+                            return Err($crate::types::ParseError::merge(errors));
+                        }
+                        Ok(res)
+                    }
+                    _ => Err($crate::types::ParseError::expected_type(value)),
+                }
+            }
+        }
+
+        impl<T: $crate::types::ParseFromParameter + $($bound)*> $crate::types::ParseFromParameter
+            for $ty<T>
+        {
+            fn parse_from_parameter(value: Option<&str>) -> $crate::types::ParseResult<Self> {
+                match value {
+                    Some(value) => {
+                        Self::parse_from_parameters(&value.split(',').collect::<Vec<_>>())
+                    }
+                    None => Err($crate::types::ParseError::expected_input()),
+                }
+            }
+
+            fn parse_from_parameters(values: &[&str]) -> $crate::types::ParseResult<Self> {
+                if values.is_empty() {
+                    return Err($crate::types::ParseError::expected_input());
+                }
+
+                let mut res: Self = ($new)(values.len());
+                for value in values {
+                    if !res.insert(
+                        T::parse_from_parameter(Some(value))
+                            .map_err($crate::types::ParseError::propagate)?,
+                    ) {
+                        return Err($crate::types::ParseError::custom(
+                            "the items in the list must be unique.",
+                        ));
+                    }
+                }
+                Ok(res)
+            }
+        }
+
+        impl<T: $crate::types::ToJSON + $($bound)*> $crate::types::ToJSON for $ty<T> {
+            fn to_json(&self) -> ::serde_json::Value {
+                let mut values = Vec::with_capacity(self.len());
+                for item in self {
+                    values.push(item.to_json());
+                }
+                ::serde_json::Value::Array(values)
+            }
+        }
+    };
+}
+
+macro_rules! impl_smart_pointer_type {
+    ($ty:ident) => {
+        impl<T: $crate::types::Type> $crate::types::Type for $ty<T> {
+            const NAME: $crate::types::TypeName = T::NAME;
+            const IS_REQUIRED: bool = T::IS_REQUIRED;
+
+            type ValueType = T::ValueType;
+
+            fn schema_ref() -> $crate::registry::MetaSchemaRef {
+                T::schema_ref()
+            }
+
+            fn register(registry: &mut $crate::registry::Registry) {
+                T::register(registry);
+            }
+
+            fn as_value(&self) -> Option<&Self::ValueType> {
+                self.as_ref().as_value()
+            }
+        }
+
+        impl<T: $crate::types::ParseFromJSON> $crate::types::ParseFromJSON for $ty<T> {
+            fn parse_from_json(value: ::serde_json::Value) -> $crate::types::ParseResult<Self> {
+                T::parse_from_json(value)
+                    .map($ty::new)
+                    .map_err($crate::types::ParseError::propagate)
+            }
+        }
+
+        impl<T: $crate::types::ParseFromParameter> $crate::types::ParseFromParameter for $ty<T> {
+            fn parse_from_parameter(value: Option<&str>) -> $crate::types::ParseResult<Self> {
+                T::parse_from_parameter(value)
+                    .map($ty::new)
+                    .map_err($crate::types::ParseError::propagate)
+            }
+
+            fn parse_from_parameters(values: &[&str]) -> $crate::types::ParseResult<Self> {
+                T::parse_from_parameters(values)
+                    .map($ty::new)
+                    .map_err($crate::types::ParseError::propagate)
+            }
+        }
+
+        impl<T: $crate::types::ToJSON> $crate::types::ToJSON for $ty<T> {
+            fn to_json(&self) -> ::serde_json::Value {
+                self.as_ref().to_json()
+            }
+        }
+    };
+}
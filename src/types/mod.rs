@@ -75,6 +75,14 @@ pub trait Type: Sized + Send + Sync {
     fn as_value(&self) -> Option<&Self::ValueType>;
 }
 
+/// Represents an object type, it is implemented by `#[derive(Object)]`.
+pub trait IsObjectType: Type {
+    /// If true, the object rejects the unknown fields, so it cannot be
+    /// flattened or extended by another object.
+    #[doc(hidden)]
+    const DENY_UNKNOWN_FIELDS: bool = false;
+}
+
 /// Represents a type that can parsing from JSON.
 pub trait ParseFromJSON: Type {
     /// Parse from [`serde_json::Value`].
@@ -86,6 +94,12 @@ pub trait ParseFromJSON: Type {
 pub trait ParseFromParameter: Type {
     /// Parse from parameter.
     fn parse_from_parameter(value: Option<&str>) -> ParseResult<Self>;
+
+    /// Parse from multiple values of a parameter, such as a query parameter
+    /// that appears more than once.
+    fn parse_from_parameters(values: &[&str]) -> ParseResult<Self> {
+        Self::parse_from_parameter(values.first().copied())
+    }
 }
 
 /// Represents a type that can parsing from multipart.
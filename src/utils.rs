@@ -0,0 +1,29 @@
+use std::{any::TypeId, collections::HashMap, sync::RwLock};
+
+use once_cell::sync::OnceCell;
+
+/// A value built only once for each type, and leaked so that it can be
+/// borrowed for the rest of the program.
+pub(crate) struct TypeCache<V: ?Sized + 'static>(OnceCell<RwLock<HashMap<TypeId, &'static V>>>);
+
+impl<V: ?Sized + Sync + 'static> TypeCache<V> {
+    pub(crate) const fn new() -> Self {
+        Self(OnceCell::new())
+    }
+
+    /// Returns the value of `T`, calling `init` to build it the first time.
+    pub(crate) fn get_or_init<T: 'static>(
+        &'static self,
+        init: impl FnOnce() -> Box<V>,
+    ) -> &'static V {
+        let values = self.0.get_or_init(Default::default);
+        if let Some(value) = values.read().unwrap().get(&TypeId::of::<T>()) {
+            return value;
+        }
+        values
+            .write()
+            .unwrap()
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::leak(init()))
+    }
+}
@@ -29,6 +29,10 @@ impl<T: Deref<Target = [E]>, E: Type> Validator<T> for MaxItems {
 }
 
 impl ValidatorMeta for MaxItems {
+    fn name(&self) -> &'static str {
+        "maxItems"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.max_items = Some(self.len);
     }
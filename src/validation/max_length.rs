@@ -26,6 +26,10 @@ impl<T: AsRef<str>> Validator<T> for MaxLength {
 }
 
 impl ValidatorMeta for MaxLength {
+    fn name(&self) -> &'static str {
+        "maxLength"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.max_length = Some(self.len);
     }
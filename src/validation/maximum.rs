@@ -32,6 +32,10 @@ impl<T: AsPrimitive<f64>> Validator<T> for Maximum {
 }
 
 impl ValidatorMeta for Maximum {
+    fn name(&self) -> &'static str {
+        "maximum"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.maximum = Some(self.n);
         if self.exclusive {
@@ -29,6 +29,10 @@ impl<T: Deref<Target = [E]>, E: Type> Validator<T> for MinItems {
 }
 
 impl ValidatorMeta for MinItems {
+    fn name(&self) -> &'static str {
+        "minItems"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.min_items = Some(self.len);
     }
@@ -26,6 +26,10 @@ impl<T: AsRef<str>> Validator<T> for MinLength {
 }
 
 impl ValidatorMeta for MinLength {
+    fn name(&self) -> &'static str {
+        "minLength"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.min_length = Some(self.len);
     }
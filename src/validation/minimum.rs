@@ -32,6 +32,10 @@ impl<T: AsPrimitive<f64>> Validator<T> for Minimum {
 }
 
 impl ValidatorMeta for Minimum {
+    fn name(&self) -> &'static str {
+        "minimum"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.minimum = Some(self.n);
         if self.exclusive {
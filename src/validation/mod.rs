@@ -20,12 +20,35 @@ pub use multiple_of::MultipleOf;
 pub use pattern::Pattern;
 pub use unique_items::UniqueItems;
 
-use crate::registry::MetaSchema;
+use serde_json::Value;
+
+use crate::{registry::MetaSchema, Violation};
 
 pub trait Validator<T>: Display {
     fn check(&self, value: &T) -> bool;
 }
 
 pub trait ValidatorMeta {
+    fn name(&self) -> &'static str;
+
     fn update_meta(&self, meta: &mut MetaSchema);
 }
+
+/// Create a violation for a value that failed the validator.
+#[doc(hidden)]
+pub fn violation<V: ValidatorMeta + Display>(
+    validator: &V,
+    pointer: String,
+    actual: Option<Value>,
+) -> Violation {
+    let mut schema = MetaSchema::ANY;
+    validator.update_meta(&mut schema);
+    Violation {
+        pointer,
+        parameter: None,
+        validator: Some(validator.name()),
+        expected: serde_json::to_value(&schema).ok(),
+        actual,
+        message: format!("verification failed. {}", validator),
+    }
+}
@@ -27,6 +27,10 @@ impl<T: AsPrimitive<f64>> Validator<T> for MultipleOf {
 }
 
 impl ValidatorMeta for MultipleOf {
+    fn name(&self) -> &'static str {
+        "multipleOf"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.multiple_of = Some(self.n);
     }
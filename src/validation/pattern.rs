@@ -27,6 +27,10 @@ impl<T: AsRef<str>> Validator<T> for Pattern {
 }
 
 impl ValidatorMeta for Pattern {
+    fn name(&self) -> &'static str {
+        "pattern"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.pattern = Some(self.pattern.to_string());
     }
@@ -33,6 +33,10 @@ impl<T: Deref<Target = [E]>, E: Type + Eq + Hash> Validator<T> for UniqueItems {
 }
 
 impl ValidatorMeta for UniqueItems {
+    fn name(&self) -> &'static str {
+        "uniqueItems"
+    }
+
     fn update_meta(&self, meta: &mut MetaSchema) {
         meta.unique_items = Some(true);
     }
@@ -0,0 +1,73 @@
+use std::fs;
+
+use poem_openapi::{
+    cli::{run_with_args, CliError, Options, SpecFormat},
+    OpenApi, OpenApiService,
+};
+
+struct Api;
+
+#[OpenApi]
+impl Api {
+    #[oai(path = "/hello", method = "get")]
+    async fn hello(&self) {}
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn parse_options() {
+    assert_eq!(
+        Options::parse(args(&[])).unwrap(),
+        Options {
+            format: SpecFormat::Json,
+            output: None,
+            check: false,
+        }
+    );
+    assert_eq!(
+        Options::parse(args(&["--format", "yaml", "-o", "a.yaml", "--check"])).unwrap(),
+        Options {
+            format: SpecFormat::Yaml,
+            output: Some("a.yaml".into()),
+            check: true,
+        }
+    );
+    assert!(matches!(
+        Options::parse(args(&["--format", "xml"])),
+        Err(CliError::InvalidArgs(_))
+    ));
+    assert!(matches!(
+        Options::parse(args(&["--check"])),
+        Err(CliError::InvalidArgs(_))
+    ));
+}
+
+#[test]
+fn write_and_check() {
+    let service = OpenApiService::new(Api).title("Test");
+    let path = std::env::temp_dir().join(format!("poem-openapi-cli-{}.yaml", std::process::id()));
+    let path_str = path.to_str().unwrap();
+
+    assert!(matches!(
+        run_with_args(&service, args(&["-f", "yaml", "-o", path_str, "--check"])),
+        Err(CliError::OutOfDate(_))
+    ));
+
+    run_with_args(&service, args(&["-f", "yaml", "-o", path_str])).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), service.spec_yaml());
+    run_with_args(&service, args(&["-f", "yaml", "-o", path_str, "--check"])).unwrap();
+
+    fs::write(&path, format!("{}\n", service.spec_yaml())).unwrap();
+    run_with_args(&service, args(&["-f", "yaml", "-o", path_str, "--check"])).unwrap();
+
+    fs::write(&path, "openapi: 3.0.0").unwrap();
+    assert!(matches!(
+        run_with_args(&service, args(&["-f", "yaml", "-o", path_str, "--check"])),
+        Err(CliError::OutOfDate(_))
+    ));
+
+    fs::remove_file(&path).unwrap();
+}
@@ -8,8 +8,9 @@ use poem_openapi::{
         multipart::{JsonField, Upload},
         Binary,
     },
-    Multipart, ParseRequestError,
+    Multipart, ParseRequestError, Violation,
 };
+use serde_json::json;
 
 fn create_multipart_payload(parts: &[(&str, Option<&str>, &[u8])]) -> Vec<u8> {
     let mut data = Vec::new();
@@ -105,7 +106,8 @@ async fn required_fields() {
     assert_eq!(
         err,
         ParseRequestError::ParseRequestBody {
-            reason: "field `file` is required".to_string()
+            reason: "field `file` is required".to_string(),
+            violations: Vec::new(),
         }
     );
 }
@@ -272,7 +274,15 @@ async fn validator() {
         err,
         ParseRequestError::ParseRequestBody {
             reason: r#"field `value` verification failed. maximum(32, exclusive: false)"#
-                .to_string()
+                .to_string(),
+            violations: vec![Violation {
+                pointer: "/value".to_string(),
+                parameter: None,
+                validator: Some("maximum"),
+                expected: Some(json!({ "maximum": 32.0 })),
+                actual: None,
+                message: "verification failed. maximum(32, exclusive: false)".to_string(),
+            }],
         }
     );
 }
@@ -424,7 +434,8 @@ async fn repeated_error() {
         err,
         ParseRequestError::ParseRequestBody {
             reason: "failed to parse field `value`: failed to parse \"string\": repeated field"
-                .to_string()
+                .to_string(),
+            violations: Vec::new(),
         }
     )
 }
@@ -1,8 +1,13 @@
 use poem_openapi::{
-    registry::{MetaSchema, Registry},
+    registry::{MetaAdditionalProperties, MetaSchema, Registry},
     types::{ParseFromJSON, ToJSON, Type},
     Object,
 };
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
 use serde_json::json;
 
 fn get_meta<T: Type>() -> MetaSchema {
@@ -325,3 +330,539 @@ fn serde_generic() {
         Obj { a: 10 }
     );
 }
+
+#[test]
+fn field_flatten() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Inner {
+        b: i32,
+        c: Option<i32>,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Obj {
+        a: i32,
+        #[oai(flatten)]
+        inner: Inner,
+    }
+
+    let meta = get_meta::<Obj>();
+    assert_eq!(
+        meta.properties
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>(),
+        vec!["a", "b", "c"]
+    );
+    assert_eq!(meta.required, vec!["a", "b"]);
+
+    assert_eq!(
+        Obj::parse_from_json(json!({
+            "a": 10,
+            "b": 20,
+        }))
+        .unwrap(),
+        Obj {
+            a: 10,
+            inner: Inner { b: 20, c: None }
+        }
+    );
+
+    assert_eq!(
+        Obj {
+            a: 10,
+            inner: Inner { b: 20, c: Some(30) }
+        }
+        .to_json(),
+        json!({
+            "a": 10,
+            "b": 20,
+            "c": 30,
+        })
+    );
+}
+
+#[test]
+fn field_extends() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Base {
+        id: i32,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    #[oai(extends = "Base")]
+    struct Obj {
+        #[oai(extends)]
+        base: Base,
+        name: String,
+    }
+
+    let mut registry = Registry::new();
+    Obj::register(&mut registry);
+    assert!(registry.schemas.contains_key("Base"));
+
+    let meta = registry.schemas.remove("Obj").unwrap();
+    assert!(meta.properties.is_empty());
+    assert_eq!(meta.all_of.len(), 2);
+    assert_eq!(meta.all_of[0], Base::schema_ref());
+    let own = meta.all_of[1].unwrap_inline();
+    assert_eq!(own.ty, "object");
+    assert_eq!(own.properties[0].0, "name");
+    assert_eq!(own.required, vec!["name"]);
+
+    assert_eq!(
+        Obj::parse_from_json(json!({
+            "id": 1,
+            "name": "abc",
+        }))
+        .unwrap(),
+        Obj {
+            base: Base { id: 1 },
+            name: "abc".to_string(),
+        }
+    );
+
+    assert_eq!(
+        Obj {
+            base: Base { id: 1 },
+            name: "abc".to_string(),
+        }
+        .to_json(),
+        json!({
+            "id": 1,
+            "name": "abc",
+        })
+    );
+}
+
+#[test]
+fn extends_type_alias() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Base {
+        id: i32,
+    }
+
+    type Alias = Base;
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    #[oai(extends = "Base")]
+    struct Obj {
+        #[oai(extends)]
+        base: Alias,
+        name: String,
+    }
+
+    let meta = get_meta::<Obj>();
+    assert_eq!(meta.all_of[0], Base::schema_ref());
+    assert_eq!(meta.all_of[1].unwrap_inline().properties[0].0, "name");
+}
+
+#[test]
+fn flatten_extended_object() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Base {
+        id: i32,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    #[oai(extends = "Base")]
+    struct Inner {
+        #[oai(extends)]
+        base: Base,
+        name: String,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Obj {
+        #[oai(flatten)]
+        inner: Inner,
+        value: i32,
+    }
+
+    let meta = get_meta::<Obj>();
+    assert_eq!(
+        meta.properties
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>(),
+        vec!["id", "name", "value"]
+    );
+    assert_eq!(meta.required, vec!["id", "name", "value"]);
+}
+
+#[test]
+fn field_read_only_and_write_only() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Obj {
+        #[oai(read_only, default)]
+        id: i32,
+        name: String,
+        #[oai(write_only)]
+        password: String,
+    }
+
+    let meta = get_meta::<Obj>();
+    assert!(meta.properties[0].1.unwrap_inline().read_only);
+    assert!(!meta.properties[0].1.unwrap_inline().write_only);
+    assert!(!meta.properties[1].1.unwrap_inline().read_only);
+    assert!(!meta.properties[1].1.unwrap_inline().write_only);
+    assert!(meta.properties[2].1.unwrap_inline().write_only);
+
+    assert_eq!(
+        Obj::parse_from_json(json!({
+            "id": 100,
+            "name": "abc",
+            "password": "123456",
+        }))
+        .unwrap(),
+        Obj {
+            id: 0,
+            name: "abc".to_string(),
+            password: "123456".to_string(),
+        }
+    );
+
+    assert_eq!(
+        Obj {
+            id: 100,
+            name: "abc".to_string(),
+            password: "123456".to_string(),
+        }
+        .to_json(),
+        json!({
+            "id": 100,
+            "name": "abc",
+        })
+    );
+}
+
+#[test]
+fn field_read_only_reference() {
+    #[derive(Object, Debug, Default, Eq, PartialEq)]
+    struct Inner {
+        a: i32,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Obj {
+        #[oai(read_only, default)]
+        inner: Inner,
+    }
+
+    let meta = get_meta::<Obj>();
+    let schema = meta.properties[0].1.unwrap_inline();
+    assert!(schema.read_only);
+    assert_eq!(schema.all_of, vec![Inner::schema_ref()]);
+}
+
+#[test]
+fn deny_unknown_fields() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    #[oai(deny_unknown_fields)]
+    struct Obj {
+        a: i32,
+        #[oai(name = "c")]
+        b: i32,
+        #[oai(read_only, default)]
+        id: i32,
+    }
+
+    assert_eq!(
+        get_meta::<Obj>().additional_properties,
+        Some(MetaAdditionalProperties::Bool(false))
+    );
+
+    assert_eq!(
+        Obj::parse_from_json(json!({
+            "a": 1,
+            "c": 2,
+        }))
+        .unwrap(),
+        Obj { a: 1, b: 2, id: 0 }
+    );
+
+    let err = Obj::parse_from_json(json!({
+        "a": 1,
+        "b": 2,
+        "c": 3,
+        "d/e": 4,
+        "id": 5,
+    }))
+    .unwrap_err();
+    assert_eq!(
+        err.violations()
+            .iter()
+            .map(|violation| (violation.pointer.as_str(), violation.message.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("/b", "failed to parse \"Obj\": unknown field `b`."),
+            ("/d~1e", "failed to parse \"Obj\": unknown field `d/e`."),
+            ("/id", "failed to parse \"Obj\": unknown field `id`."),
+        ]
+    );
+}
+
+#[test]
+fn map_fields() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Inner {
+        a: i32,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Obj {
+        values: HashMap<String, i32>,
+        objects: BTreeMap<String, Inner>,
+    }
+
+    let mut registry = Registry::new();
+    Obj::register(&mut registry);
+    assert!(registry.schemas.contains_key("Inner"));
+
+    let meta = registry.schemas.remove("Obj").unwrap();
+    let schema = meta.properties[0].1.unwrap_inline();
+    assert_eq!(schema.ty, "object");
+    assert_eq!(
+        schema.additional_properties,
+        Some(MetaAdditionalProperties::Schema(
+            Box::new(i32::schema_ref())
+        ))
+    );
+    let schema = meta.properties[1].1.unwrap_inline();
+    assert_eq!(
+        schema.additional_properties,
+        Some(MetaAdditionalProperties::Schema(Box::new(
+            Inner::schema_ref()
+        )))
+    );
+
+    let obj = Obj {
+        values: vec![("x".to_string(), 1), ("y".to_string(), 2)]
+            .into_iter()
+            .collect(),
+        objects: vec![("z".to_string(), Inner { a: 3 })]
+            .into_iter()
+            .collect(),
+    };
+    let value = json!({
+        "values": { "x": 1, "y": 2 },
+        "objects": { "z": { "a": 3 } },
+    });
+    assert_eq!(obj.to_json(), value);
+    assert_eq!(Obj::parse_from_json(value).unwrap(), obj);
+}
+
+#[test]
+fn std_fields() {
+    #[derive(Object, Debug, PartialEq)]
+    struct Obj {
+        a: bool,
+        b: usize,
+        c: isize,
+        d: Box<i32>,
+        e: Arc<String>,
+        f: BTreeSet<i32>,
+        g: [i32; 2],
+        h: serde_json::Value,
+        i: char,
+        j: i128,
+        k: u128,
+        l: (i32, String),
+    }
+
+    let meta = get_meta::<Obj>();
+    assert_eq!(meta.properties[0].1.unwrap_inline().ty, "boolean");
+    assert_eq!(meta.properties[1].1.unwrap_inline().format, Some("uint64"));
+    assert_eq!(meta.properties[2].1.unwrap_inline().format, Some("int64"));
+    assert_eq!(meta.properties[3].1, i32::schema_ref());
+    assert_eq!(meta.properties[4].1, String::schema_ref());
+    let schema = meta.properties[5].1.unwrap_inline();
+    assert_eq!(schema.ty, "array");
+    assert_eq!(schema.unique_items, Some(true));
+    let schema = meta.properties[6].1.unwrap_inline();
+    assert_eq!(schema.min_items, Some(2));
+    assert_eq!(schema.max_items, Some(2));
+    assert_eq!(meta.properties[7].1.unwrap_inline(), &MetaSchema::ANY);
+    let schema = meta.properties[8].1.unwrap_inline();
+    assert_eq!(schema.ty, "string");
+    assert_eq!(schema.max_length, Some(1));
+    assert_eq!(schema.min_length, Some(1));
+    assert_eq!(meta.properties[9].1.unwrap_inline().format, Some("int128"));
+    assert_eq!(
+        meta.properties[10].1.unwrap_inline().format,
+        Some("uint128")
+    );
+    let schema = meta.properties[11].1.unwrap_inline();
+    assert_eq!(schema.ty, "array");
+    assert_eq!(
+        schema.prefix_items,
+        vec![i32::schema_ref(), String::schema_ref()]
+    );
+    assert_eq!(schema.min_items, Some(2));
+    assert_eq!(schema.max_items, Some(2));
+
+    let obj = Obj {
+        a: true,
+        b: 1,
+        c: -1,
+        d: Box::new(2),
+        e: Arc::new("abc".to_string()),
+        f: vec![3, 4].into_iter().collect(),
+        g: [5, 6],
+        h: json!({ "x": [1, null] }),
+        i: 'x',
+        j: i128::MIN,
+        k: u128::MAX,
+        l: (7, "abc".to_string()),
+    };
+    let value = json!({
+        "a": true,
+        "b": 1,
+        "c": -1,
+        "d": 2,
+        "e": "abc",
+        "f": [3, 4],
+        "g": [5, 6],
+        "h": { "x": [1, null] },
+        "i": "x",
+        "j": i128::MIN.to_string(),
+        "k": u128::MAX.to_string(),
+        "l": [7, "abc"],
+    });
+    assert_eq!(obj.to_json(), value);
+    assert_eq!(Obj::parse_from_json(value).unwrap(), obj);
+
+    assert!(BTreeSet::<i32>::parse_from_json(json!([1, 1])).is_err());
+    assert!(<[i32; 2]>::parse_from_json(json!([1, 2, 3])).is_err());
+    assert_eq!(u64::parse_from_json(json!(u64::MAX)).unwrap(), u64::MAX);
+    assert!(char::parse_from_json(json!("ab")).is_err());
+    assert_eq!(i128::parse_from_json(json!(-1)).unwrap(), -1);
+    assert!(<(i32, String)>::parse_from_json(json!([1])).is_err());
+    assert_eq!(
+        <(i32, String)>::parse_from_json(json!(["1", 2]))
+            .unwrap_err()
+            .violations()
+            .iter()
+            .map(|violation| violation.pointer.as_str())
+            .collect::<Vec<_>>(),
+        vec!["/0", "/1"]
+    );
+}
+
+#[test]
+fn ip_fields() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    #[derive(Object, Debug, PartialEq)]
+    struct Obj {
+        a: Ipv4Addr,
+        b: Ipv6Addr,
+        c: IpAddr,
+    }
+
+    let meta = get_meta::<Obj>();
+    assert_eq!(meta.properties[0].1.unwrap_inline().format, Some("ipv4"));
+    assert_eq!(meta.properties[1].1.unwrap_inline().format, Some("ipv6"));
+    assert_eq!(meta.properties[2].1.unwrap_inline().format, None);
+    assert_eq!(
+        meta.properties[2].1.unwrap_inline().one_of,
+        vec![Ipv4Addr::schema_ref(), Ipv6Addr::schema_ref()]
+    );
+
+    let obj = Obj {
+        a: Ipv4Addr::LOCALHOST,
+        b: Ipv6Addr::LOCALHOST,
+        c: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+    };
+    let value = json!({
+        "a": "127.0.0.1",
+        "b": "::1",
+        "c": "10.0.0.1",
+    });
+    assert_eq!(obj.to_json(), value);
+    assert_eq!(Obj::parse_from_json(value).unwrap(), obj);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_fields() {
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+    #[derive(Object, Debug, PartialEq)]
+    struct Obj {
+        a: DateTime<Utc>,
+        b: NaiveDate,
+    }
+
+    let meta = get_meta::<Obj>();
+    assert_eq!(
+        meta.properties[0].1.unwrap_inline().format,
+        Some("date-time")
+    );
+    assert_eq!(meta.properties[1].1.unwrap_inline().format, Some("date"));
+
+    let obj = Obj {
+        a: Utc.ymd(2021, 9, 1).and_hms(10, 20, 30),
+        b: NaiveDate::from_ymd(2021, 9, 1),
+    };
+    let value = json!({
+        "a": "2021-09-01T10:20:30+00:00",
+        "b": "2021-09-01",
+    });
+    assert_eq!(obj.to_json(), value);
+    assert_eq!(Obj::parse_from_json(value).unwrap(), obj);
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_fields() {
+    use time::{macros::datetime, PrimitiveDateTime};
+
+    #[derive(Object, Debug, PartialEq)]
+    struct Obj {
+        a: PrimitiveDateTime,
+    }
+
+    let meta = get_meta::<Obj>();
+    assert_eq!(
+        meta.properties[0].1.unwrap_inline().format,
+        Some("naive-date-time")
+    );
+
+    let obj = Obj {
+        a: datetime!(2021-09-01 10:20:30.5),
+    };
+    let value = json!({
+        "a": "2021-09-01T10:20:30.5",
+    });
+    assert_eq!(obj.to_json(), value);
+    assert_eq!(Obj::parse_from_json(value).unwrap(), obj);
+    assert_eq!(
+        Obj::parse_from_json(json!({"a": "2021-09-01T10:20:30"}))
+            .unwrap()
+            .a,
+        datetime!(2021-09-01 10:20:30)
+    );
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn decimal_fields() {
+    use rust_decimal::Decimal;
+
+    #[derive(Object, Debug, PartialEq)]
+    struct Obj {
+        a: Decimal,
+    }
+
+    let meta = get_meta::<Obj>();
+    assert_eq!(meta.properties[0].1.unwrap_inline().format, Some("decimal"));
+
+    let obj = Obj {
+        a: Decimal::new(1234, 2),
+    };
+    assert_eq!(obj.to_json(), json!({"a": "12.34"}));
+    assert_eq!(Obj::parse_from_json(json!({"a": "12.34"})).unwrap(), obj);
+    assert_eq!(Obj::parse_from_json(json!({"a": 12.34})).unwrap(), obj);
+    assert!(Obj::parse_from_json(json!({"a": true})).is_err());
+}
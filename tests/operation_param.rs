@@ -1,10 +1,12 @@
+use std::collections::HashMap;
+
 use poem::{
     http::{header, Method, StatusCode, Uri},
     web::Cookie,
     Endpoint, IntoEndpoint, Request,
 };
 use poem_openapi::{
-    registry::{MetaApi, MetaParamIn, MetaSchema, MetaSchemaRef},
+    registry::{MetaApi, MetaParamIn, MetaParamStyle, MetaSchema, MetaSchemaRef},
     types::Type,
     OpenApi, OpenApiService,
 };
@@ -277,3 +279,260 @@ async fn default_opt() {
         .await;
     assert_eq!(resp.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn query_array() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "get")]
+        async fn test(&self, #[oai(name = "v", in = "query")] v: Vec<i32>) {
+            assert_eq!(v, vec![1, 2, 3]);
+        }
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    assert_eq!(meta.paths[0].operations[0].params[0].style, None);
+    assert_eq!(meta.paths[0].operations[0].params[0].explode, None);
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/?v=1&v=2&v=3"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn query_array_missing() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/required", method = "get")]
+        async fn required(&self, #[oai(name = "v", in = "query")] _v: Vec<i32>) {}
+
+        #[oai(path = "/optional", method = "get")]
+        async fn optional(&self, #[oai(name = "v", in = "query")] v: Option<Vec<i32>>) {
+            assert_eq!(v, None);
+        }
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    assert!(meta.paths[0].operations[0].params[0].required);
+    assert!(!meta.paths[1].operations[0].params[0].required);
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/required"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/optional"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn query_array_delimited() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/form", method = "get")]
+        async fn form(&self, #[oai(name = "v", in = "query", explode = false)] v: Vec<i32>) {
+            assert_eq!(v, vec![1, 2, 3]);
+        }
+
+        #[oai(path = "/pipe", method = "get")]
+        async fn pipe(
+            &self,
+            #[oai(name = "v", in = "query", style = "pipeDelimited", explode = false)] v: Vec<i32>,
+        ) {
+            assert_eq!(v, vec![1, 2, 3]);
+        }
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    assert_eq!(meta.paths[1].operations[0].params[0].explode, Some(false));
+    assert_eq!(
+        meta.paths[1].operations[0].params[0].style,
+        Some(MetaParamStyle::PipeDelimited)
+    );
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/form?v=1,2,3"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/pipe?v=1|2|3"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn query_deep_object() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "get")]
+        async fn test(
+            &self,
+            #[oai(name = "filter", in = "query", style = "deepObject")] filter: HashMap<
+                String,
+                i32,
+            >,
+        ) {
+            assert_eq!(filter.len(), 2);
+            assert_eq!(filter["a"], 1);
+            assert_eq!(filter["b"], 2);
+        }
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    assert_eq!(
+        meta.paths[0].operations[0].params[0].style,
+        Some(MetaParamStyle::DeepObject)
+    );
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/?filter[a]=1&filter[b]=2&other=3"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn query_scalar_not_exploded() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "get")]
+        async fn test(&self, #[oai(name = "v", in = "query", explode = false)] v: String) {
+            assert_eq!(v, "a,b,c");
+        }
+    }
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/?v=a,b,c"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn query_form_object() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/exploded", method = "get")]
+        async fn exploded(
+            &self,
+            #[oai(name = "filter", in = "query")] filter: HashMap<String, i32>,
+            #[oai(name = "page", in = "query")] page: i32,
+        ) {
+            assert_eq!(filter.len(), 2);
+            assert_eq!(filter["a"], 1);
+            assert_eq!(filter["b"], 2);
+            assert_eq!(page, 3);
+        }
+
+        #[oai(path = "/form", method = "get")]
+        async fn form(
+            &self,
+            #[oai(name = "filter", in = "query", explode = false)] filter: HashMap<String, i32>,
+        ) {
+            assert_eq!(filter.len(), 2);
+            assert_eq!(filter["a"], 1);
+            assert_eq!(filter["b"], 2);
+        }
+    }
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/exploded?a=1&b=2&page=3"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/form?filter=a,1,b,2"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn query_form_object_with_security_scheme() {
+    use poem_openapi::{auth::ApiKey, SecurityScheme};
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "api_key", key_name = "key", in = "query")]
+    struct MyApiKey(ApiKey);
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "get")]
+        async fn test(
+            &self,
+            #[oai(name = "filter", in = "query")] filter: HashMap<String, i32>,
+            #[oai(auth)] api_key: MyApiKey,
+        ) {
+            assert_eq!(filter.len(), 1);
+            assert_eq!(filter["a"], 1);
+            assert_eq!(api_key.0.key, "abc");
+        }
+    }
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/?a=1&key=abc"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
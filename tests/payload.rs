@@ -0,0 +1,667 @@
+use std::collections::BTreeMap;
+
+use futures_util::stream::{self, BoxStream, StreamExt};
+use poem::{http::StatusCode, Endpoint, IntoEndpoint, IntoResponse, Request};
+use poem_openapi::{
+    payload::{
+        Attachment, AttachmentType, Binary, BinaryStream, EventStream, Form, NdJson, Payload,
+        PlainText, Xml,
+    },
+    registry::{MetaMediaType, MetaSchema, MetaSchemaRef, MetaXml, Registry},
+    types::Type,
+    Enum, Object, OpenApi, OpenApiService, ParseRequestError,
+};
+
+#[tokio::test]
+async fn form() {
+    #[derive(Enum, Debug, Eq, PartialEq)]
+    enum GrantType {
+        Password,
+        RefreshToken,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    #[oai(rename_fields = "snake_case")]
+    struct TokenRequest {
+        grant_type: GrantType,
+        #[oai(max_length = 5)]
+        username: String,
+        remember: bool,
+        age: Option<i32>,
+        scopes: Vec<String>,
+    }
+
+    assert_eq!(
+        <Form<TokenRequest>>::CONTENT_TYPE,
+        "application/x-www-form-urlencoded"
+    );
+    assert_eq!(
+        <Form<TokenRequest>>::schema_ref(),
+        MetaSchemaRef::Reference("TokenRequest")
+    );
+
+    let parse = |content_type: &'static str, data: &'static str| async move {
+        let (request, mut body) = Request::builder()
+            .content_type(content_type)
+            .body(data)
+            .split();
+        <Form<TokenRequest>>::from_request(&request, &mut body).await
+    };
+
+    assert_eq!(
+        parse(
+            "application/x-www-form-urlencoded",
+            "grant_type=PASSWORD&username=sunli&remember=on&age=18&scopes=read&scopes=write"
+        )
+        .await
+        .unwrap(),
+        Form(TokenRequest {
+            grant_type: GrantType::Password,
+            username: "sunli".to_string(),
+            remember: true,
+            age: Some(18),
+            scopes: vec!["read".to_string(), "write".to_string()],
+        })
+    );
+
+    assert_eq!(
+        parse(
+            "application/x-www-form-urlencoded; charset=utf-8",
+            "grant_type=REFRESH_TOKEN&username=abc&remember=false"
+        )
+        .await
+        .unwrap(),
+        Form(TokenRequest {
+            grant_type: GrantType::RefreshToken,
+            username: "abc".to_string(),
+            remember: false,
+            age: None,
+            scopes: vec![],
+        })
+    );
+
+    assert_eq!(
+        parse(
+            "application/x-www-form-urlencoded",
+            "grant_type=PASSWORD&username=&remember=off&age="
+        )
+        .await
+        .unwrap(),
+        Form(TokenRequest {
+            grant_type: GrantType::Password,
+            username: "".to_string(),
+            remember: false,
+            age: None,
+            scopes: vec![],
+        })
+    );
+
+    let err = parse(
+        "application/x-www-form-urlencoded",
+        "grant_type=PASSWORD&username=abcdef&remember=true",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err.violations()
+            .iter()
+            .map(|violation| (violation.pointer.as_str(), violation.validator))
+            .collect::<Vec<_>>(),
+        vec![("/username", Some("maxLength"))]
+    );
+
+    assert_eq!(
+        parse("application/json", "{}").await.unwrap_err(),
+        ParseRequestError::ContentTypeNotSupported {
+            content_type: "application/json".to_string()
+        }
+    );
+}
+
+#[tokio::test]
+async fn xml() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Category {
+        #[oai(xml(attribute))]
+        id: i64,
+        name: String,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Pet {
+        #[oai(xml(attribute, name = "ID"))]
+        id: i64,
+        #[oai(xml(prefix = "p", namespace = "http://example.com/schema"))]
+        name: String,
+        category: Category,
+        #[oai(xml(name = "photoUrl"))]
+        photo_urls: Vec<String>,
+        #[oai(xml(name = "tag", wrapped))]
+        tags: Vec<String>,
+        age: Option<i32>,
+    }
+
+    let mut registry = Registry::new();
+    Pet::register(&mut registry);
+    let schema = &registry.schemas["Pet"];
+    assert_eq!(
+        schema.properties[0].1.unwrap_inline().xml,
+        Some(MetaXml {
+            name: Some("ID"),
+            attribute: true,
+            ..MetaXml::default()
+        })
+    );
+    assert_eq!(
+        schema.properties[1].1.unwrap_inline().xml,
+        Some(MetaXml {
+            namespace: Some("http://example.com/schema"),
+            prefix: Some("p"),
+            ..MetaXml::default()
+        })
+    );
+    assert_eq!(schema.properties[2].1, MetaSchemaRef::Reference("Category"));
+    assert_eq!(
+        schema.properties[4].1.unwrap_inline().xml,
+        Some(MetaXml {
+            name: Some("tag"),
+            wrapped: true,
+            ..MetaXml::default()
+        })
+    );
+
+    let parse = |content_type: &'static str, data: &'static str| async move {
+        let (request, mut body) = Request::builder()
+            .content_type(content_type)
+            .body(data)
+            .split();
+        <Xml<Pet>>::from_request(&request, &mut body).await
+    };
+
+    let pet = Pet {
+        id: 1,
+        name: "dog & cat".to_string(),
+        category: Category {
+            id: 2,
+            name: "pets".to_string(),
+        },
+        photo_urls: vec!["a.png".to_string(), "b.png".to_string()],
+        tags: vec!["x".to_string(), "y".to_string()],
+        age: None,
+    };
+
+    assert_eq!(
+        parse(
+            "application/xml",
+            r#"<?xml version="1.0"?>
+            <Pet ID="1">
+                <p:name xmlns:p="http://example.com/schema">dog &amp; cat</p:name>
+                <category id="2"><name>pets</name></category>
+                <photoUrl>a.png</photoUrl>
+                <photoUrl>b.png</photoUrl>
+                <tag><tag>x</tag><tag>y</tag></tag>
+            </Pet>"#
+        )
+        .await
+        .unwrap(),
+        Xml(pet)
+    );
+
+    let body = Xml(Pet {
+        id: 1,
+        name: "dog & cat".to_string(),
+        category: Category {
+            id: 2,
+            name: "pets".to_string(),
+        },
+        photo_urls: vec!["a.png".to_string()],
+        tags: vec!["x".to_string()],
+        age: Some(3),
+    })
+    .into_response()
+    .take_body()
+    .into_string()
+    .await
+    .unwrap();
+    assert_eq!(
+        body,
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<Pet ID="1">"#,
+            r#"<p:name xmlns:p="http://example.com/schema">dog &amp; cat</p:name>"#,
+            r#"<category id="2"><name>pets</name></category>"#,
+            r#"<photoUrl>a.png</photoUrl>"#,
+            r#"<tag><tag>x</tag></tag>"#,
+            r#"<age>3</age>"#,
+            r#"</Pet>"#
+        )
+    );
+
+    let err = parse("application/xml", "<Pet ID=\"a\"></Pet>")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ParseRequestError::ParseRequestBody { .. }));
+
+    assert_eq!(
+        parse("application/json", "{}").await.unwrap_err(),
+        ParseRequestError::ContentTypeNotSupported {
+            content_type: "application/json".to_string()
+        }
+    );
+}
+
+#[tokio::test]
+async fn xml_map() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct Labels {
+        labels: BTreeMap<String, String>,
+    }
+
+    let mut labels = BTreeMap::new();
+    labels.insert("a b".to_string(), "1".to_string());
+    labels.insert("<x>\"".to_string(), "2 & 3".to_string());
+
+    let body = Xml(Labels {
+        labels: labels.clone(),
+    })
+    .into_response()
+    .take_body()
+    .into_string()
+    .await
+    .unwrap();
+    assert_eq!(
+        body,
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<Labels><labels>"#,
+            r#"<entry key="&lt;x&gt;&quot;">2 &amp; 3</entry>"#,
+            r#"<entry key="a b">1</entry>"#,
+            r#"</labels></Labels>"#
+        )
+    );
+
+    let (request, mut body) = Request::builder()
+        .content_type("application/xml")
+        .body(body)
+        .split();
+    assert_eq!(
+        <Xml<Labels>>::from_request(&request, &mut body)
+            .await
+            .unwrap(),
+        Xml(Labels { labels })
+    );
+}
+
+#[derive(Object)]
+struct Progress {
+    percent: i32,
+}
+
+fn progress() -> BoxStream<'static, Progress> {
+    stream::iter(vec![Progress { percent: 50 }, Progress { percent: 100 }]).boxed()
+}
+
+#[tokio::test]
+async fn streaming() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/events", method = "get")]
+        async fn events(&self) -> EventStream<BoxStream<'static, Progress>> {
+            EventStream::new(progress())
+        }
+
+        #[oai(path = "/lines", method = "get")]
+        async fn lines(&self) -> NdJson<BoxStream<'static, Progress>> {
+            NdJson::new(progress())
+        }
+    }
+
+    let meta = Api::meta().remove(0);
+    for (idx, content_type) in ["text/event-stream", "application/x-ndjson"]
+        .iter()
+        .enumerate()
+    {
+        assert_eq!(
+            meta.paths[idx].operations[0].responses.responses[0].content,
+            vec![MetaMediaType {
+                content_type,
+                schema: MetaSchemaRef::Inline(MetaSchema {
+                    items: Some(Box::new(MetaSchemaRef::Reference("Progress"))),
+                    ..MetaSchema::new("array")
+                }),
+            }]
+        );
+    }
+    let mut registry = Registry::new();
+    Api::register(&mut registry);
+    assert!(registry.schemas.contains_key("Progress"));
+
+    let api = OpenApiService::new(Api).into_endpoint();
+
+    let mut resp = api
+        .call(Request::builder().uri("/events".parse().unwrap()).finish())
+        .await;
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/event-stream"
+    );
+    assert_eq!(
+        resp.take_body().into_string().await.unwrap(),
+        "data: {\"percent\":50}\n\ndata: {\"percent\":100}\n\n"
+    );
+
+    let mut resp = api
+        .call(Request::builder().uri("/lines".parse().unwrap()).finish())
+        .await;
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/x-ndjson"
+    );
+    assert_eq!(
+        resp.take_body().into_string().await.unwrap(),
+        "{\"percent\":50}\n{\"percent\":100}\n"
+    );
+}
+
+#[tokio::test]
+async fn body_limit() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/stream", method = "post", body_limit = 5)]
+        async fn stream(&self, data: BinaryStream) -> PlainText {
+            match data.into_vec().await {
+                Ok(data) => PlainText(data.len().to_string()),
+                Err(err) => PlainText(err.to_string()),
+            }
+        }
+
+        #[oai(path = "/buffered", method = "post", body_limit = 5)]
+        async fn buffered(&self, data: Binary) -> PlainText {
+            PlainText(data.0.len().to_string())
+        }
+    }
+
+    let meta = Api::meta().remove(0);
+    assert_eq!(
+        meta.paths[0].operations[0]
+            .responses
+            .responses
+            .iter()
+            .map(|resp| resp.status)
+            .collect::<Vec<_>>(),
+        vec![Some(200), Some(400), Some(413), Some(415)]
+    );
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let call = |path: &'static str, content_length: Option<&'static str>, data: &'static str| {
+        let mut req = Request::builder()
+            .method(poem::http::Method::POST)
+            .uri(path.parse().unwrap());
+        if let Some(content_length) = content_length {
+            req = req.header("content-length", content_length);
+        }
+        api.call(req.body(data))
+    };
+
+    let mut resp = call("/stream", None, "abc").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "3");
+
+    let mut resp = call("/buffered", Some("5"), "abcde").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "5");
+
+    let resp = call("/stream", Some("1073741824"), "abcdef").await;
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let resp = call("/stream", None, "abcdef").await;
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let resp = call("/buffered", None, "abcdef").await;
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn attachment() {
+    let path = std::env::temp_dir().join(format!(
+        "poem-openapi-attachment-{}.txt",
+        std::process::id()
+    ));
+    std::fs::write(&path, "0123456789").unwrap();
+
+    struct Api(std::path::PathBuf);
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/file", method = "get")]
+        async fn file(&self) -> Attachment {
+            Attachment::open(&self.0)
+                .await
+                .unwrap()
+                .filename("数据.txt")
+                .attachment_type(AttachmentType::Inline)
+        }
+    }
+
+    let meta = Api::meta().remove(0);
+    let responses = &meta.paths[0].operations[0].responses.responses;
+    assert_eq!(
+        responses.iter().map(|resp| resp.status).collect::<Vec<_>>(),
+        vec![Some(200), Some(206), Some(304), Some(416)]
+    );
+    assert!(responses[1]
+        .headers
+        .iter()
+        .any(|header| header.name == "CONTENT-RANGE"));
+
+    let api = OpenApiService::new(Api(path.clone())).into_endpoint();
+    let call = |headers: &[(&'static str, String)]| {
+        let mut req = Request::builder().uri("/file".parse().unwrap());
+        for (name, value) in headers {
+            req = req.header(*name, value.as_str());
+        }
+        api.call(req.finish())
+    };
+
+    let mut resp = call(&[]).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("content-disposition").unwrap(),
+        "inline; filename*=UTF-8''%E6%95%B0%E6%8D%AE.txt"
+    );
+    assert_eq!(resp.headers().get("content-length").unwrap(), "10");
+    assert_eq!(resp.headers().get("accept-ranges").unwrap(), "bytes");
+    assert!(resp.headers().contains_key("last-modified"));
+    let etag = resp
+        .headers()
+        .get("etag")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "0123456789");
+
+    let mut resp = call(&[("range", "bytes=2-4".to_string())]).await;
+    assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(resp.headers().get("content-range").unwrap(), "bytes 2-4/10");
+    assert_eq!(resp.headers().get("content-length").unwrap(), "3");
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "234");
+
+    let mut resp = call(&[("range", "bytes=-3".to_string())]).await;
+    assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "789");
+
+    let resp = call(&[("range", "bytes=20-".to_string())]).await;
+    assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(resp.headers().get("content-range").unwrap(), "bytes */10");
+
+    let mut resp = call(&[
+        ("range", "bytes=2-4".to_string()),
+        ("if-range", "\"other\"".to_string()),
+    ])
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "0123456789");
+
+    let resp = call(&[("if-none-match", etag)]).await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn attachment_response() {
+    let path = std::env::temp_dir().join(format!(
+        "poem-openapi-attachment-response-{}.txt",
+        std::process::id()
+    ));
+    std::fs::write(&path, "0123456789").unwrap();
+
+    #[derive(poem_openapi::Response)]
+    enum FileResponse {
+        #[oai(status = 200)]
+        Ok(Attachment),
+        #[oai(status = 404)]
+        NotFound,
+    }
+
+    struct Api(std::path::PathBuf);
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/file", method = "get")]
+        async fn file(&self) -> FileResponse {
+            match Attachment::open(&self.0).await {
+                Ok(attachment) => FileResponse::Ok(attachment),
+                Err(_) => FileResponse::NotFound,
+            }
+        }
+    }
+
+    let api = OpenApiService::new(Api(path.clone())).into_endpoint();
+    let call = |name: &'static str, value: String| {
+        api.call(
+            Request::builder()
+                .uri("/file".parse().unwrap())
+                .header(name, value)
+                .finish(),
+        )
+    };
+
+    let mut resp = call("range", "bytes=2-4".to_string()).await;
+    assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "234");
+
+    let resp = call("range", "bytes=20-".to_string()).await;
+    assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+
+    let resp = call("accept", "*/*".to_string()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let etag = resp.headers().get("etag").unwrap().to_str().unwrap();
+    let resp = call("if-none-match", etag.to_string()).await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+#[derive(Object, Debug, Eq, PartialEq)]
+struct Device {
+    #[oai(max_length = 8)]
+    name: String,
+    battery: i32,
+    tags: Vec<String>,
+}
+
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+async fn round_trip<P>(value: P) -> Result<P, ParseRequestError>
+where
+    P: Payload + IntoResponse,
+{
+    assert_eq!(P::schema_ref(), MetaSchemaRef::Reference("Device"));
+    let mut resp = value.into_response();
+    assert_eq!(resp.headers().get("content-type").unwrap(), P::CONTENT_TYPE);
+    let data = resp.take_body().into_vec().await.unwrap();
+    let (request, mut body) = Request::builder()
+        .content_type(P::CONTENT_TYPE)
+        .body(data)
+        .split();
+    P::from_request(&request, &mut body).await
+}
+
+#[cfg(feature = "msgpack")]
+#[tokio::test]
+async fn msgpack() {
+    use poem_openapi::payload::MsgPack;
+
+    let device = Device {
+        name: "phone".to_string(),
+        battery: 80,
+        tags: vec!["a".to_string()],
+    };
+    assert_eq!(round_trip(MsgPack(device)).await.unwrap().0.battery, 80);
+
+    let err = round_trip(MsgPack(Device {
+        name: "a long name".to_string(),
+        battery: 80,
+        tags: vec![],
+    }))
+    .await
+    .unwrap_err();
+    assert_eq!(err.violations()[0].pointer, "/name");
+
+    let (request, mut body) = Request::builder()
+        .content_type("application/msgpack")
+        .body(vec![0xc1])
+        .split();
+    let err = <MsgPack<Device>>::from_request(&request, &mut body)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        err.into_response().headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+}
+
+#[cfg(feature = "cbor")]
+#[tokio::test]
+async fn cbor() {
+    use poem_openapi::payload::Cbor;
+
+    let device = Device {
+        name: "phone".to_string(),
+        battery: 80,
+        tags: vec!["a".to_string()],
+    };
+    assert_eq!(
+        round_trip(Cbor(device)).await.unwrap().0.tags,
+        vec!["a".to_string()]
+    );
+
+    let err = round_trip(Cbor(Device {
+        name: "a long name".to_string(),
+        battery: 80,
+        tags: vec![],
+    }))
+    .await
+    .unwrap_err();
+    assert_eq!(err.violations()[0].pointer, "/name");
+
+    let (request, mut body) = Request::builder()
+        .content_type("application/cbor")
+        .body(vec![0xff])
+        .split();
+    let err = <Cbor<Device>>::from_request(&request, &mut body)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        err.into_response().headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+}
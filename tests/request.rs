@@ -1,7 +1,12 @@
+use poem::{
+    http::{StatusCode, Uri},
+    route::Route,
+    Endpoint, IntoEndpoint, IntoResponse,
+};
 use poem_openapi::{
     payload::{Json, PlainText},
     registry::{MetaMediaType, MetaRequest, MetaSchema, MetaSchemaRef},
-    Object, Request,
+    Object, OpenApi, OpenApiService, ParseRequestError, Request,
 };
 
 #[derive(Debug, Object, Eq, PartialEq)]
@@ -69,3 +74,117 @@ async fn from_request() {
         MyRequest::CreateByPlainText(PlainText("abcdef".to_string()))
     );
 }
+
+#[tokio::test]
+async fn content_type_not_supported() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "post")]
+        async fn create(&self, _req: MyRequest) {}
+
+        #[oai(path = "/users/:id", method = "get")]
+        async fn get(&self, #[oai(name = "id", in = "path")] _id: i32) {}
+    }
+
+    let api: Route = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            poem::Request::builder()
+                .method(poem::http::Method::POST)
+                .content_type("application/xml")
+                .body("<user/>"),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let resp = api
+        .call(
+            poem::Request::builder()
+                .method(poem::http::Method::POST)
+                .body("abc"),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let err: poem::Error = ParseRequestError::ContentTypeNotSupported {
+        content_type: "application/xml".to_string(),
+    }
+    .into();
+    assert_eq!(err.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn error_handler() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "post")]
+        async fn create(&self, _req: MyRequest) {}
+
+        #[oai(path = "/users/:id", method = "get")]
+        async fn get(&self, #[oai(name = "id", in = "path")] _id: i32) {}
+    }
+
+    let api: Route = OpenApiService::new(Api)
+        .error_handler(|err| {
+            (
+                StatusCode::IM_A_TEAPOT,
+                format!("{}: {}", err.status().as_u16(), err),
+            )
+                .into_response()
+        })
+        .into_endpoint();
+    let mut resp = api
+        .call(
+            poem::Request::builder()
+                .method(poem::http::Method::POST)
+                .content_type("application/xml")
+                .body("<user/>"),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::IM_A_TEAPOT);
+    assert_eq!(
+        resp.take_body().into_string().await.unwrap(),
+        "415: the content type `application/xml` is not supported."
+    );
+
+    let resp = api
+        .call(
+            poem::Request::builder()
+                .method(poem::http::Method::POST)
+                .content_type("text/plain")
+                .body("abc"),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = api
+        .call(
+            poem::Request::builder()
+                .uri(Uri::from_static("/users/abc"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::IM_A_TEAPOT);
+
+    let resp = api
+        .call(
+            poem::Request::builder()
+                .uri(Uri::from_static("/users/1"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = api
+        .call(
+            poem::Request::builder()
+                .uri(Uri::from_static("/abc"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -1,13 +1,17 @@
 mod request;
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use poem::{
-    http::{HeaderValue, StatusCode},
-    IntoResponse,
+    http::{HeaderValue, Method, StatusCode},
+    Endpoint, IntoEndpoint, IntoResponse,
 };
 use poem_openapi::{
-    payload::{Json, PlainText},
-    registry::{MetaHeader, MetaMediaType, MetaResponse, MetaResponses, MetaSchema, MetaSchemaRef},
-    Object, Response,
+    payload::{Json, Negotiate, PlainText},
+    registry::{
+        MetaHeader, MetaMediaType, MetaResponse, MetaResponses, MetaSchema, MetaSchemaRef, Registry,
+    },
+    Object, OpenApi, OpenApiService, Response,
 };
 use serde_json::Value;
 
@@ -197,3 +201,181 @@ async fn headers() {
         Some(&HeaderValue::from_static("abc"))
     );
 }
+
+#[test]
+fn error_responses() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/a", method = "post")]
+        async fn a(&self, #[oai(name = "n", in = "query")] _n: i32, _req: Json<i32>) -> MyResponse {
+            MyResponse::Ok
+        }
+
+        #[oai(path = "/b", method = "get")]
+        async fn b(&self) -> MyResponse {
+            MyResponse::Ok
+        }
+
+        #[oai(path = "/c", method = "get", skip_error_responses)]
+        async fn c(&self, #[oai(name = "n", in = "query")] _n: i32) -> PlainText {
+            PlainText("c".to_string())
+        }
+
+        #[oai(path = "/d", method = "get")]
+        async fn d(&self) -> Negotiate<String, (Json<String>, PlainText)> {
+            Negotiate::new("d".to_string())
+        }
+    }
+
+    let meta = Api::meta().remove(0);
+    let statuses = |idx: usize| {
+        meta.paths[idx].operations[0]
+            .responses
+            .responses
+            .iter()
+            .map(|resp| resp.status)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(statuses(0), vec![Some(200), Some(400), None, Some(415)]);
+    assert_eq!(statuses(1), vec![Some(200), Some(400), None]);
+    assert_eq!(statuses(2), vec![Some(200)]);
+    assert_eq!(statuses(3), vec![Some(200), Some(406)]);
+
+    assert_eq!(
+        meta.paths[0].operations[0].responses.responses[3],
+        MetaResponse {
+            description: Some("Unsupported Media Type"),
+            status: Some(415),
+            content: vec![MetaMediaType {
+                content_type: "application/problem+json",
+                schema: MetaSchemaRef::Reference("Error"),
+            }],
+            headers: vec![]
+        }
+    );
+
+    let mut registry = Registry::new();
+    Api::register(&mut registry);
+    let schema = &registry.schemas["Error"];
+    assert_eq!(
+        schema
+            .properties
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>(),
+        vec![
+            "type",
+            "title",
+            "status",
+            "detail",
+            "parameter",
+            "parameters",
+            "violations"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn negotiate() {
+    #[derive(Response)]
+    enum MyResponse {
+        #[oai(status = 200)]
+        Ok(Negotiate<String, (Json<String>, PlainText)>),
+    }
+
+    assert_eq!(
+        MyResponse::meta().responses[0].content,
+        vec![
+            MetaMediaType {
+                content_type: "application/json",
+                schema: MetaSchemaRef::Inline(MetaSchema::new("string")),
+            },
+            MetaMediaType {
+                content_type: "text/plain",
+                schema: MetaSchemaRef::Inline(MetaSchema::new("string")),
+            }
+        ]
+    );
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "get")]
+        async fn test(&self) -> MyResponse {
+            MyResponse::Ok(Negotiate::new("abc".to_string()))
+        }
+    }
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let call = |accept: Option<&'static str>| {
+        let mut req = poem::Request::builder();
+        if let Some(accept) = accept {
+            req = req.header("accept", accept);
+        }
+        api.call(req.finish())
+    };
+
+    for (accept, content_type, body) in [
+        (None, "application/json", "\"abc\""),
+        (Some("*/*"), "application/json", "\"abc\""),
+        (Some("text/plain"), "text/plain", "abc"),
+        (Some("text/*"), "text/plain", "abc"),
+        (
+            Some("text/plain;q=0.5, application/json;q=0.8"),
+            "application/json",
+            "\"abc\"",
+        ),
+        (Some("application/json;q=0, */*"), "text/plain", "abc"),
+        (Some("*/*, text/plain"), "text/plain", "abc"),
+        (
+            Some("text/*, application/json"),
+            "application/json",
+            "\"abc\"",
+        ),
+    ] {
+        let mut resp = call(accept).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("vary").unwrap(), "accept");
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            content_type,
+            "accept: {:?}",
+            accept
+        );
+        assert_eq!(resp.take_body().into_string().await.unwrap(), body);
+    }
+
+    let resp = call(Some("image/png")).await;
+    assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE);
+}
+
+#[tokio::test]
+async fn negotiate_before_handler() {
+    static CALLED: AtomicBool = AtomicBool::new(false);
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "post")]
+        async fn test(&self) -> Negotiate<String, (Json<String>, PlainText)> {
+            CALLED.store(true, Ordering::SeqCst);
+            Negotiate::new("abc".to_string())
+        }
+    }
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let resp = api
+        .call(
+            poem::Request::builder()
+                .method(Method::POST)
+                .header("accept", "image/png")
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE);
+    assert!(!CALLED.load(Ordering::SeqCst));
+}
@@ -1,7 +1,8 @@
+use poem::{http::StatusCode, Endpoint, IntoEndpoint, Request};
 use poem_openapi::{
-    auth::{Basic, Bearer},
+    auth::{ApiKey, Basic, Bearer, ScopeChecker},
     registry::{MetaSecurityScheme, Registry},
-    OpenApi, SecurityScheme,
+    OpenApi, OpenApiService, ParseRequestError, SecurityScheme,
 };
 
 #[test]
@@ -96,3 +97,848 @@ fn bearer_auth() {
         }
     );
 }
+
+#[tokio::test]
+async fn check_scopes() {
+    #[derive(SecurityScheme)]
+    #[oai(
+        type = "oauth2",
+        flows(implicit(
+            authorization_url = "https://example.com/authorize",
+            scope(name = "read", desc = "Read data"),
+            scope(name = "write", desc = "Write data"),
+        )),
+        check_scopes
+    )]
+    struct MySecurityScheme(Bearer);
+
+    impl ScopeChecker for MySecurityScheme {
+        fn has_scope(&self, scope: &str) -> bool {
+            self.0.token.split('+').any(|granted| granted == scope)
+        }
+    }
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, #[oai(auth("read", "write"))] _auth: MySecurityScheme) {}
+    }
+
+    let meta = MyApi::meta().remove(0);
+    assert_eq!(
+        meta.paths[0].operations[0]
+            .responses
+            .responses
+            .iter()
+            .map(|resp| resp.status)
+            .collect::<Vec<_>>(),
+        vec![Some(200), Some(401), Some(403)]
+    );
+    assert_eq!(
+        meta.paths[0].operations[0].security,
+        vec![vec![("my_security_scheme", vec!["read", "write"])]
+            .into_iter()
+            .collect::<std::collections::HashMap<_, _>>()]
+    );
+
+    let api = OpenApiService::new(MyApi).into_endpoint();
+    let call = |token: &'static str| {
+        api.call(
+            Request::builder()
+                .uri("/test".parse().unwrap())
+                .header("authorization", format!("Bearer {}", token))
+                .finish(),
+        )
+    };
+
+    assert_eq!(call("read+write").await.status(), StatusCode::OK);
+
+    let mut resp = call("read").await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let body: serde_json::Value =
+        serde_json::from_str(&resp.take_body().into_string().await.unwrap()).unwrap();
+    assert_eq!(body["detail"], "missing scopes: write");
+}
+
+#[tokio::test]
+async fn optional() {
+    use std::collections::HashMap;
+
+    use poem_openapi::payload::PlainText;
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "bearer")]
+    struct MySecurityScheme(Bearer);
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, #[oai(auth)] auth: Option<MySecurityScheme>) -> PlainText {
+            PlainText(match auth {
+                Some(auth) => auth.0.token,
+                None => "anonymous".to_string(),
+            })
+        }
+    }
+
+    let meta = MyApi::meta().remove(0);
+    assert_eq!(
+        meta.paths[0].operations[0]
+            .responses
+            .responses
+            .iter()
+            .map(|resp| resp.status)
+            .collect::<Vec<_>>(),
+        vec![Some(200)]
+    );
+    assert_eq!(
+        meta.paths[0].operations[0].security,
+        vec![
+            vec![("my_security_scheme", vec![])]
+                .into_iter()
+                .collect::<HashMap<_, _>>(),
+            HashMap::new(),
+        ]
+    );
+
+    let api = OpenApiService::new(MyApi).into_endpoint();
+    let mut resp = api
+        .call(Request::builder().uri("/test".parse().unwrap()).finish())
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "anonymous");
+
+    let mut resp = api
+        .call(
+            Request::builder()
+                .uri("/test".parse().unwrap())
+                .header("authorization", "Bearer abc")
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "abc");
+}
+
+#[cfg(feature = "jwt")]
+#[tokio::test]
+async fn jwt() {
+    use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
+    use poem::{middleware::AddData, EndpointExt};
+    use poem_openapi::{
+        auth::{Jwt, JwtKey, JwtKeys},
+        payload::PlainText,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Claims {
+        sub: String,
+        exp: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        iss: Option<String>,
+    }
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "bearer")]
+    struct MySecurityScheme(Jwt<Claims>);
+
+    let mut registry = Registry::new();
+    MySecurityScheme::register(&mut registry);
+    assert_eq!(
+        registry
+            .security_schemes
+            .get("my_security_scheme")
+            .unwrap()
+            .bearer_format,
+        Some("JWT")
+    );
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, #[oai(auth)] auth: MySecurityScheme) -> PlainText {
+            PlainText(auth.0.claims.sub)
+        }
+    }
+
+    let token = |kid: Option<&str>, secret: &str, exp: u64, iss: Option<&str>| {
+        let header = Header {
+            kid: kid.map(ToString::to_string),
+            ..Header::default()
+        };
+        let claims = Claims {
+            sub: "sunli".to_string(),
+            exp,
+            iss: iss.map(ToString::to_string),
+        };
+        encode(
+            &header,
+            &claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    };
+    let now = get_current_timestamp();
+
+    let api = OpenApiService::new(MyApi)
+        .into_endpoint()
+        .with(AddData::new(
+            JwtKeys::new()
+                .key(JwtKey::hmac("secret"))
+                .issuer("https://example.com"),
+        ));
+    let call = |token: String| {
+        api.call(
+            Request::builder()
+                .uri("/test".parse().unwrap())
+                .header("authorization", format!("Bearer {}", token))
+                .finish(),
+        )
+    };
+
+    let mut resp = call(token(None, "secret", now + 60, Some("https://example.com"))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "sunli");
+
+    for token in vec![
+        token(None, "other", now + 60, Some("https://example.com")),
+        token(None, "secret", now - 120, Some("https://example.com")),
+        token(None, "secret", now + 60, Some("https://other.com")),
+        token(None, "secret", now + 60, None),
+    ] {
+        assert_eq!(call(token).await.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let api = OpenApiService::new(MyApi)
+        .into_endpoint()
+        .with(AddData::new(
+            JwtKeys::new()
+                .jwks(
+                    r#"{"keys": [
+                    {"kty": "oct", "kid": "k1", "alg": "HS256", "k": "c2VjcmV0MQ"},
+                    {"kty": "oct", "kid": "k2", "alg": "HS256", "k": "c2VjcmV0Mg"}
+                ]}"#,
+                )
+                .unwrap(),
+        ));
+    let call = |token: String| {
+        api.call(
+            Request::builder()
+                .uri("/test".parse().unwrap())
+                .header("authorization", format!("Bearer {}", token))
+                .finish(),
+        )
+    };
+    assert_eq!(
+        call(token(Some("k2"), "secret2", now + 60, None))
+            .await
+            .status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        call(token(Some("k1"), "secret2", now + 60, None))
+            .await
+            .status(),
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[cfg(feature = "jwt")]
+#[tokio::test]
+async fn jwt_keys_per_scheme() {
+    use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
+    use poem::{middleware::AddData, EndpointExt, IntoResponse};
+    use poem_openapi::{
+        auth::{Jwt, JwtKey, JwtKeys},
+        payload::PlainText,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Claims {
+        sub: String,
+        exp: u64,
+    }
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "bearer")]
+    struct UserAuth(Jwt<Claims>);
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "bearer")]
+    struct AdminAuth(Jwt<Claims, AdminAuth>);
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/user", method = "get")]
+        async fn user(&self, #[oai(auth)] auth: UserAuth) -> PlainText {
+            PlainText(auth.0.claims.sub)
+        }
+
+        #[oai(path = "/admin", method = "get")]
+        async fn admin(&self, #[oai(auth)] auth: AdminAuth) -> PlainText {
+            PlainText(auth.0.claims.sub)
+        }
+    }
+
+    let token = |secret: &str| {
+        let claims = Claims {
+            sub: "sunli".to_string(),
+            exp: get_current_timestamp() + 60,
+        };
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    };
+
+    async fn call<E: Endpoint>(api: &E, path: &'static str, token: String) -> StatusCode {
+        api.call(
+            Request::builder()
+                .uri(path.parse().unwrap())
+                .header("authorization", format!("Bearer {}", token))
+                .finish(),
+        )
+        .await
+        .into_response()
+        .status()
+    }
+
+    let api = OpenApiService::new(MyApi)
+        .into_endpoint()
+        .with(AddData::new(JwtKeys::new().key(JwtKey::hmac("user"))))
+        .with(AddData::new(
+            JwtKeys::for_scheme::<AdminAuth>().key(JwtKey::hmac("admin")),
+        ));
+    assert_eq!(call(&api, "/user", token("user")).await, StatusCode::OK);
+    assert_eq!(
+        call(&api, "/user", token("admin")).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(call(&api, "/admin", token("admin")).await, StatusCode::OK);
+    assert_eq!(
+        call(&api, "/admin", token("user")).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    let api = OpenApiService::new(MyApi)
+        .into_endpoint()
+        .with(AddData::new(JwtKeys::new().key(JwtKey::hmac("user"))));
+    assert_eq!(
+        call(&api, "/admin", token("admin")).await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
+
+#[tokio::test]
+async fn checker() {
+    use std::collections::HashMap;
+
+    use poem::{middleware::AddData, EndpointExt};
+    use poem_openapi::payload::PlainText;
+
+    struct User {
+        name: String,
+        admin: bool,
+    }
+
+    async fn api_key_checker(req: &Request, api_key: ApiKey) -> Option<User> {
+        let users = req.extensions().get::<HashMap<&'static str, bool>>()?;
+        let admin = *users.get(api_key.key.as_str())?;
+        Some(User {
+            name: api_key.key,
+            admin,
+        })
+    }
+
+    async fn admin_checker(req: &Request, api_key: ApiKey) -> Result<User, ParseRequestError> {
+        match api_key_checker(req, api_key).await {
+            Some(user) if user.admin => Ok(user),
+            Some(_) => Err(ParseRequestError::Forbidden),
+            None => Err(ParseRequestError::Authorization),
+        }
+    }
+
+    #[derive(SecurityScheme)]
+    #[oai(
+        type = "api_key",
+        key_name = "X-API-Key",
+        in = "header",
+        checker = "api_key_checker"
+    )]
+    struct UserAuth(User);
+
+    #[derive(SecurityScheme)]
+    #[oai(
+        type = "api_key",
+        key_name = "X-API-Key",
+        in = "header",
+        checker = "admin_checker"
+    )]
+    struct AdminAuth(User);
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/user", method = "get")]
+        async fn user(&self, #[oai(auth)] auth: UserAuth) -> PlainText {
+            PlainText(auth.0.name)
+        }
+
+        #[oai(path = "/admin", method = "get")]
+        async fn admin(&self, #[oai(auth)] auth: AdminAuth) -> PlainText {
+            PlainText(auth.0.name)
+        }
+
+        #[oai(path = "/maybe_admin", method = "get")]
+        async fn maybe_admin(&self, #[oai(auth)] auth: Option<AdminAuth>) -> PlainText {
+            PlainText(auth.map(|auth| auth.0.name).unwrap_or_default())
+        }
+    }
+
+    let mut users = HashMap::new();
+    users.insert("alice", true);
+    users.insert("bob", false);
+    let api = OpenApiService::new(MyApi)
+        .into_endpoint()
+        .with(AddData::new(users));
+    let call = |path: &'static str, key: &'static str| {
+        api.call(
+            Request::builder()
+                .uri(path.parse().unwrap())
+                .header("x-api-key", key)
+                .finish(),
+        )
+    };
+
+    let mut resp = call("/user", "bob").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "bob");
+    assert_eq!(
+        call("/user", "eve").await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+
+    let mut resp = call("/admin", "alice").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "alice");
+    assert_eq!(call("/admin", "bob").await.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        call("/admin", "eve").await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+
+    let mut resp = call("/maybe_admin", "alice").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "alice");
+    assert_eq!(
+        call("/maybe_admin", "bob").await.status(),
+        StatusCode::FORBIDDEN
+    );
+    let mut resp = call("/maybe_admin", "eve").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "");
+}
+
+#[tokio::test]
+async fn bearer_checker() {
+    use poem_openapi::{auth::BearerAuthorization, payload::PlainText};
+
+    struct Token(String);
+
+    impl BearerAuthorization for Token {
+        const BEARER_FORMAT: Option<&'static str> = Some("opaque");
+
+        fn from_request(req: &Request) -> Result<Self, ParseRequestError> {
+            Bearer::from_request(req).map(|bearer| Token(bearer.token))
+        }
+    }
+
+    struct User {
+        name: String,
+    }
+
+    async fn token_checker(_req: &Request, token: Token) -> Option<User> {
+        token.0.strip_prefix("user-").map(|name| User {
+            name: name.to_string(),
+        })
+    }
+
+    async fn bearer_checker(_req: &Request, bearer: Bearer) -> Option<User> {
+        Some(User { name: bearer.token })
+    }
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "bearer", checker = "token_checker")]
+    struct TokenAuth(User);
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "bearer", checker = "bearer_checker")]
+    struct MyBearerAuth(User);
+
+    let mut registry = Registry::new();
+    TokenAuth::register(&mut registry);
+    MyBearerAuth::register(&mut registry);
+    assert_eq!(
+        registry.security_schemes["token_auth"].bearer_format,
+        Some("opaque")
+    );
+    assert_eq!(
+        registry.security_schemes["my_bearer_auth"].bearer_format,
+        None
+    );
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, #[oai(auth)] auth: TokenAuth) -> PlainText {
+            PlainText(auth.0.name)
+        }
+    }
+
+    let api = OpenApiService::new(MyApi).into_endpoint();
+    let call = |token: &'static str| {
+        api.call(
+            Request::builder()
+                .uri("/test".parse().unwrap())
+                .header("authorization", format!("Bearer {}", token))
+                .finish(),
+        )
+    };
+
+    let mut resp = call("user-sunli").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "sunli");
+    assert_eq!(call("sunli").await.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn multiple_requirements() {
+    use std::collections::HashMap;
+
+    use poem_openapi::payload::PlainText;
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "api_key", key_name = "X-API-Key", in = "header")]
+    struct MyApiKey(ApiKey);
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "bearer", check_scopes)]
+    struct MyBearer(Bearer);
+
+    impl ScopeChecker for MyBearer {
+        fn has_scope(&self, scope: &str) -> bool {
+            self.0.token.split('+').any(|granted| granted == scope)
+        }
+    }
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "api_key", key_name = "X-Client-Cert", in = "header")]
+    struct MyClientCert(ApiKey);
+
+    #[derive(SecurityScheme)]
+    enum MyAuth {
+        ApiKey(MyApiKey),
+        Bearer(MyBearer),
+    }
+
+    assert_eq!(MyAuth::security_schemes(), vec!["my_api_key", "my_bearer"]);
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/alternatives", method = "get")]
+        async fn alternatives(&self, #[oai(auth("read"))] auth: MyAuth) -> PlainText {
+            match auth {
+                MyAuth::ApiKey(api_key) => PlainText(api_key.0.key),
+                MyAuth::Bearer(bearer) => PlainText(bearer.0.token),
+            }
+        }
+
+        #[oai(path = "/combined", method = "get")]
+        async fn combined(
+            &self,
+            #[oai(auth)] api_key: MyApiKey,
+            #[oai(auth)] cert: MyClientCert,
+        ) -> PlainText {
+            PlainText(format!("{}:{}", api_key.0.key, cert.0.key))
+        }
+    }
+
+    let mut registry = Registry::new();
+    MyApi::register(&mut registry);
+    assert_eq!(
+        registry
+            .security_schemes
+            .keys()
+            .copied()
+            .collect::<Vec<_>>(),
+        vec!["my_api_key", "my_bearer", "my_client_cert"]
+    );
+
+    let meta = MyApi::meta().remove(0);
+    let requirement = |schemes: &[(&'static str, Vec<&'static str>)]| {
+        schemes.iter().cloned().collect::<HashMap<_, _>>()
+    };
+    assert_eq!(
+        meta.paths[0].operations[0].security,
+        vec![
+            requirement(&[("my_api_key", vec![])]),
+            requirement(&[("my_bearer", vec![])]),
+        ]
+    );
+    assert_eq!(
+        meta.paths[1].operations[0].security,
+        vec![requirement(&[
+            ("my_api_key", vec![]),
+            ("my_client_cert", vec![])
+        ])]
+    );
+
+    let api = OpenApiService::new(MyApi).into_endpoint();
+    let call = |path: &'static str, headers: &[(&'static str, &'static str)]| {
+        let mut req = Request::builder().uri(path.parse().unwrap());
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        api.call(req.finish())
+    };
+
+    assert_eq!(
+        call("/alternatives", &[("x-api-key", "abc")])
+            .await
+            .status(),
+        StatusCode::FORBIDDEN
+    );
+    let mut resp = call("/alternatives", &[("authorization", "Bearer read")]).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "read");
+    assert_eq!(
+        call("/alternatives", &[("authorization", "Bearer def")])
+            .await
+            .status(),
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        call("/alternatives", &[]).await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+
+    let mut resp = call(
+        "/combined",
+        &[("x-api-key", "abc"), ("x-client-cert", "def")],
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.take_body().into_string().await.unwrap(), "abc:def");
+    assert_eq!(
+        call("/combined", &[("x-api-key", "abc")]).await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[tokio::test]
+async fn default_security() {
+    use std::collections::HashMap;
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "api_key", key_name = "X-API-Key", in = "header")]
+    struct MyApiKey(ApiKey);
+
+    #[derive(SecurityScheme)]
+    #[oai(type = "bearer")]
+    struct MyBearer(Bearer);
+
+    struct MyApi;
+
+    #[OpenApi(security = "MyApiKey")]
+    impl MyApi {
+        #[oai(path = "/default", method = "get")]
+        async fn default(&self) {}
+
+        #[oai(path = "/override", method = "get")]
+        async fn override_security(&self, #[oai(auth)] _auth: MyBearer) {}
+    }
+
+    let meta = MyApi::meta().remove(0);
+    let mut requirement = HashMap::new();
+    requirement.insert("my_api_key", vec![]);
+    assert_eq!(meta.paths[0].operations[0].security, vec![requirement]);
+    assert_eq!(
+        meta.paths[0].operations[0]
+            .responses
+            .responses
+            .iter()
+            .map(|resp| resp.status)
+            .collect::<Vec<_>>(),
+        vec![Some(200), Some(401)]
+    );
+    let mut requirement = HashMap::new();
+    requirement.insert("my_bearer", vec![]);
+    assert_eq!(meta.paths[1].operations[0].security, vec![requirement]);
+
+    let api = OpenApiService::new(MyApi).into_endpoint();
+    let call = |path: &'static str, name: &'static str, value: &'static str| {
+        api.call(
+            Request::builder()
+                .uri(path.parse().unwrap())
+                .header(name, value)
+                .finish(),
+        )
+    };
+    assert_eq!(
+        call("/default", "x-api-key", "abc").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        call("/default", "authorization", "Bearer abc")
+            .await
+            .status(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        call("/override", "authorization", "Bearer abc")
+            .await
+            .status(),
+        StatusCode::OK
+    );
+}
+
+#[test]
+fn openid_connect() {
+    #[derive(SecurityScheme)]
+    #[oai(
+        type = "openid_connect",
+        openid_connect_url = "https://example.com/.well-known/openid-configuration"
+    )]
+    struct MySecurityScheme(Bearer);
+
+    let mut registry = Registry::new();
+    MySecurityScheme::register(&mut registry);
+    assert_eq!(
+        registry.security_schemes.get("my_security_scheme").unwrap(),
+        &MetaSecurityScheme {
+            ty: "openIdConnect",
+            description: None,
+            name: None,
+            key_in: None,
+            scheme: None,
+            bearer_format: None,
+            flows: None,
+            openid_connect_url: Some("https://example.com/.well-known/openid-configuration")
+        }
+    );
+}
+
+#[cfg(feature = "jwt")]
+#[tokio::test]
+async fn id_token() {
+    use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
+    use poem::{middleware::AddData, EndpointExt};
+    use poem_openapi::{
+        auth::{IdToken, JwtKeys},
+        payload::PlainText,
+    };
+    use serde_json::json;
+
+    #[derive(SecurityScheme)]
+    #[oai(
+        type = "openid_connect",
+        openid_connect_url = "https://example.com/.well-known/openid-configuration"
+    )]
+    struct MySecurityScheme(IdToken);
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, #[oai(auth)] auth: MySecurityScheme) -> PlainText {
+            let claims = auth.0.claims;
+            PlainText(format!(
+                "{}:{}:{}:{}",
+                claims.sub,
+                claims.aud.join(","),
+                claims.email.unwrap_or_default(),
+                claims.additional["locale"]
+            ))
+        }
+    }
+
+    let jwks = r#"{"keys": [{"kty": "oct", "kid": "k1", "alg": "HS256", "k": "c2VjcmV0"}]}"#;
+    let now = get_current_timestamp();
+    let call = |keys: JwtKeys, aud: serde_json::Value| {
+        let api = OpenApiService::new(MyApi)
+            .into_endpoint()
+            .with(AddData::new(keys));
+        let header = Header {
+            kid: Some("k1".to_string()),
+            ..Header::default()
+        };
+        let claims = json!({
+            "iss": "https://example.com",
+            "sub": "sunli",
+            "aud": aud,
+            "exp": now + 60,
+            "iat": now,
+            "email": "sunli@example.com",
+            "locale": "en",
+        });
+        let token = encode(&header, &claims, &EncodingKey::from_secret(b"secret")).unwrap();
+        async move {
+            api.call(
+                Request::builder()
+                    .uri("/test".parse().unwrap())
+                    .header("authorization", format!("Bearer {}", token))
+                    .finish(),
+            )
+            .await
+        }
+    };
+    let keys = JwtKeys::new()
+        .issuer("https://example.com")
+        .audience("client")
+        .jwks(jwks)
+        .unwrap();
+
+    let mut resp = call(keys.clone(), json!("client")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.take_body().into_string().await.unwrap(),
+        r#"sunli:client:sunli@example.com:"en""#
+    );
+    let mut resp = call(keys.clone(), json!(["client", "other"])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.take_body().into_string().await.unwrap(),
+        r#"sunli:client,other:sunli@example.com:"en""#
+    );
+    assert_eq!(
+        call(keys, json!("other")).await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+
+    let keys = JwtKeys::new()
+        .issuer("https://example.com")
+        .jwks(jwks)
+        .unwrap();
+    assert_eq!(
+        call(keys, json!("client")).await.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -0,0 +1,186 @@
+use poem::{
+    http::{StatusCode, Uri},
+    Endpoint, IntoResponse, Request,
+};
+use poem_openapi::{payload::Json, Object, OpenApi, OpenApiService, OpenApiVersion};
+use serde_json::{json, Value};
+
+struct Api;
+
+#[OpenApi]
+impl Api {
+    #[oai(path = "/hello", method = "get")]
+    async fn hello(&self) {}
+}
+
+#[test]
+fn spec() {
+    let service = OpenApiService::new(Api).title("Test").version("1.0");
+
+    let doc: Value = serde_json::from_str(&service.spec()).unwrap();
+    assert_eq!(doc["openapi"], "3.0.0");
+    assert_eq!(doc["info"]["title"], "Test");
+    assert!(doc["paths"]["/hello"]["get"].is_object());
+
+    let doc_yaml: Value = serde_yaml::from_str(&service.spec_yaml()).unwrap();
+    assert_eq!(doc_yaml, doc);
+}
+
+#[tokio::test]
+async fn spec_endpoint() {
+    let service = OpenApiService::new(Api).title("Test");
+    let ep = service.spec_endpoint();
+
+    let mut resp = ep
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/openapi.json"))
+                .finish(),
+        )
+        .await
+        .into_response();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.content_type(), Some("application/json"));
+    assert_eq!(
+        resp.take_body().into_string().await.unwrap(),
+        service.spec()
+    );
+
+    let mut resp = ep
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/openapi.yaml"))
+                .finish(),
+        )
+        .await
+        .into_response();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.content_type(), Some("application/yaml"));
+    assert_eq!(
+        resp.take_body().into_string().await.unwrap(),
+        service.spec_yaml()
+    );
+}
+
+#[test]
+fn spec_v3_1() {
+    #[derive(Object)]
+    struct Owner {
+        name: String,
+    }
+
+    #[derive(Object)]
+    struct Pet {
+        #[oai(maximum(value = "100", exclusive))]
+        age: Option<i32>,
+        name: String,
+        position: (f32, f32),
+        owner: Option<Owner>,
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/pet", method = "get")]
+        async fn get(
+            &self,
+            #[oai(name = "id", in = "query", minimum(value = "0", exclusive))] _id: i32,
+            #[oai(name = "limit", in = "query")] _limit: Option<i32>,
+        ) -> Json<Pet> {
+            Json(Pet {
+                age: None,
+                name: "dog".to_string(),
+                position: (0.0, 0.0),
+                owner: None,
+            })
+        }
+    }
+
+    struct Webhooks;
+
+    #[OpenApi]
+    impl Webhooks {
+        #[oai(path = "/newPet", method = "post")]
+        async fn new_pet(&self, _pet: Json<Pet>) {}
+    }
+
+    let service = OpenApiService::new(Api).webhooks::<Webhooks>();
+    let doc: Value = serde_json::from_str(&service.spec()).unwrap();
+    assert_eq!(doc["openapi"], "3.0.0");
+    assert!(doc.get("webhooks").is_none());
+    assert_eq!(
+        doc["components"]["schemas"]["Pet"]["properties"]["age"],
+        json!({
+            "type": "integer",
+            "format": "int32",
+            "maximum": 100.0,
+            "exclusiveMaximum": true,
+        })
+    );
+    assert!(
+        doc["components"]["schemas"]["Pet"]["properties"]["position"]
+            .get("x-prefixItems")
+            .is_none()
+    );
+    assert_eq!(
+        doc["components"]["schemas"]["Pet"]["properties"]["owner"],
+        json!({ "$ref": "#/components/schemas/Owner" })
+    );
+
+    let service = service.openapi_version(OpenApiVersion::V3_1_0);
+    let doc: Value = serde_json::from_str(&service.spec()).unwrap();
+    assert_eq!(doc["openapi"], "3.1.0");
+    assert_eq!(
+        doc["components"]["schemas"]["Pet"]["properties"]["age"],
+        json!({
+            "type": ["integer", "null"],
+            "format": "int32",
+            "exclusiveMaximum": 100.0,
+        })
+    );
+    assert_eq!(
+        doc["paths"]["/pet"]["get"]["parameters"][0]["schema"],
+        json!({
+            "type": "integer",
+            "format": "int32",
+            "exclusiveMinimum": 0.0,
+        })
+    );
+    assert_eq!(
+        doc["paths"]["/pet"]["get"]["parameters"][1]["schema"],
+        json!({
+            "type": ["integer", "null"],
+            "format": "int32",
+        })
+    );
+    assert_eq!(
+        doc["components"]["schemas"]["Pet"]["properties"]["name"],
+        json!({ "type": "string" })
+    );
+    assert_eq!(
+        doc["components"]["schemas"]["Pet"]["properties"]["position"],
+        json!({
+            "type": "array",
+            "prefixItems": [
+                { "type": "number", "format": "float32" },
+                { "type": "number", "format": "float32" },
+            ],
+            "maxItems": 2,
+            "minItems": 2,
+        })
+    );
+    assert_eq!(
+        doc["components"]["schemas"]["Pet"]["properties"]["owner"],
+        json!({
+            "anyOf": [
+                { "$ref": "#/components/schemas/Owner" },
+                { "type": "null" },
+            ],
+        })
+    );
+    assert_eq!(
+        doc["webhooks"]["newPet"]["post"]["requestBody"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/Pet" })
+    );
+}
@@ -0,0 +1,343 @@
+use poem_openapi::{
+    registry::{MetaDiscriminatorObject, MetaSchema, MetaSchemaRef, Registry},
+    types::{ParseFromJSON, ToJSON, Type},
+    Object, Union,
+};
+use serde_json::json;
+
+#[derive(Object, Debug, Eq, PartialEq)]
+struct A {
+    v1: i32,
+    v2: String,
+}
+
+#[derive(Object, Debug, PartialEq)]
+struct B {
+    v3: f64,
+}
+
+#[test]
+fn with_discriminator() {
+    #[derive(Union, Debug, PartialEq)]
+    #[oai(discriminator_name = "type")]
+    enum MyObj {
+        A(A),
+        #[oai(mapping = "b")]
+        B(B),
+    }
+
+    let mut registry = Registry::new();
+    MyObj::register(&mut registry);
+    assert_eq!(MyObj::schema_ref(), MetaSchemaRef::Reference("MyObj"));
+    assert_eq!(
+        registry.schemas.remove("MyObj").unwrap(),
+        MetaSchema {
+            one_of: vec![
+                MetaSchemaRef::Reference("MyObj_A"),
+                MetaSchemaRef::Reference("MyObj_B")
+            ],
+            discriminator: Some(MetaDiscriminatorObject {
+                property_name: "type",
+                mapping: vec![
+                    ("A".to_string(), "#/components/schemas/MyObj_A".to_string()),
+                    ("b".to_string(), "#/components/schemas/MyObj_B".to_string()),
+                ]
+            }),
+            ..MetaSchema::ANY
+        }
+    );
+    assert_eq!(
+        registry.schemas.remove("MyObj_B").unwrap(),
+        MetaSchema {
+            all_of: vec![
+                MetaSchemaRef::Reference("B"),
+                MetaSchemaRef::Inline(MetaSchema {
+                    required: vec!["type"],
+                    properties: vec![(
+                        "type",
+                        MetaSchemaRef::Inline(MetaSchema {
+                            enum_items: vec![json!("b")],
+                            ..MetaSchema::new("string")
+                        })
+                    )],
+                    ..MetaSchema::new("object")
+                }),
+            ],
+            ..MetaSchema::ANY
+        }
+    );
+    assert!(registry.schemas.contains_key("MyObj_A"));
+    assert!(registry.schemas.contains_key("A"));
+    assert!(registry.schemas.contains_key("B"));
+
+    assert_eq!(
+        MyObj::parse_from_json(json!({"type": "A", "v1": 100, "v2": "hello"})).unwrap(),
+        MyObj::A(A {
+            v1: 100,
+            v2: "hello".to_string()
+        })
+    );
+    assert_eq!(
+        MyObj::parse_from_json(json!({"type": "b", "v3": 1.0})).unwrap(),
+        MyObj::B(B { v3: 1.0 })
+    );
+    assert!(MyObj::parse_from_json(json!({"type": "B", "v3": 1.0})).is_err());
+    assert!(MyObj::parse_from_json(json!({"v3": 1.0})).is_err());
+
+    assert_eq!(
+        MyObj::A(A {
+            v1: 100,
+            v2: "hello".to_string()
+        })
+        .to_json(),
+        json!({"type": "A", "v1": 100, "v2": "hello"})
+    );
+    assert_eq!(
+        MyObj::B(B { v3: 1.0 }).to_json(),
+        json!({"type": "b", "v3": 1.0})
+    );
+}
+
+#[test]
+fn without_discriminator() {
+    #[derive(Union, Debug, PartialEq)]
+    #[oai(any_of)]
+    enum MyObj {
+        A(A),
+        B(B),
+    }
+
+    let mut registry = Registry::new();
+    MyObj::register(&mut registry);
+    assert_eq!(
+        registry.schemas.remove("MyObj").unwrap(),
+        MetaSchema {
+            any_of: vec![MetaSchemaRef::Reference("A"), MetaSchemaRef::Reference("B")],
+            ..MetaSchema::ANY
+        }
+    );
+
+    assert_eq!(
+        MyObj::parse_from_json(json!({"v1": 100, "v2": "hello"})).unwrap(),
+        MyObj::A(A {
+            v1: 100,
+            v2: "hello".to_string()
+        })
+    );
+    assert_eq!(
+        MyObj::parse_from_json(json!({"v3": 1.0})).unwrap(),
+        MyObj::B(B { v3: 1.0 })
+    );
+    assert!(MyObj::parse_from_json(json!({"v4": 1.0})).is_err());
+
+    assert_eq!(MyObj::B(B { v3: 1.0 }).to_json(), json!({"v3": 1.0}));
+}
+
+#[test]
+fn serialize_schema() {
+    #[derive(Union)]
+    #[oai(discriminator_name = "type")]
+    enum MyObj {
+        A(A),
+        B(B),
+    }
+
+    let mut registry = Registry::new();
+    MyObj::register(&mut registry);
+    assert_eq!(
+        serde_json::to_value(registry.schemas.remove("MyObj").unwrap()).unwrap(),
+        json!({
+            "oneOf": [
+                {"$ref": "#/components/schemas/MyObj_A"},
+                {"$ref": "#/components/schemas/MyObj_B"},
+            ],
+            "discriminator": {
+                "propertyName": "type",
+                "mapping": {
+                    "A": "#/components/schemas/MyObj_A",
+                    "B": "#/components/schemas/MyObj_B",
+                }
+            }
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(registry.schemas.remove("MyObj_A").unwrap()).unwrap(),
+        json!({
+            "allOf": [
+                {"$ref": "#/components/schemas/A"},
+                {
+                    "type": "object",
+                    "required": ["type"],
+                    "properties": {
+                        "type": {
+                            "type": "string",
+                            "enum": ["A"],
+                        }
+                    }
+                },
+            ]
+        })
+    );
+}
+
+#[test]
+fn discriminator_with_deny_unknown_fields() {
+    #[derive(Object, Debug, PartialEq)]
+    #[oai(deny_unknown_fields)]
+    struct C {
+        v4: i32,
+    }
+
+    #[derive(Union, Debug, PartialEq)]
+    #[oai(discriminator_name = "type")]
+    enum MyObj {
+        C(C),
+    }
+
+    assert_eq!(
+        MyObj::parse_from_json(json!({"type": "C", "v4": 1})).unwrap(),
+        MyObj::C(C { v4: 1 })
+    );
+    assert!(MyObj::parse_from_json(json!({"type": "C", "v4": 1, "v5": 2})).is_err());
+    assert_eq!(
+        MyObj::C(C { v4: 1 }).to_json(),
+        json!({"type": "C", "v4": 1})
+    );
+}
+
+#[test]
+fn struct_variants() {
+    #[derive(Union, Debug, PartialEq)]
+    #[oai(discriminator_name = "type")]
+    enum Event {
+        /// Created
+        Created { id: i32, name: String },
+        #[oai(mapping = "deleted")]
+        Deleted {
+            id: i32,
+            #[oai(name = "why")]
+            reason: Option<String>,
+        },
+    }
+
+    let mut registry = Registry::new();
+    Event::register(&mut registry);
+    assert_eq!(
+        serde_json::to_value(registry.schemas.remove("Event").unwrap()).unwrap(),
+        json!({
+            "oneOf": [
+                {"$ref": "#/components/schemas/Event_Created"},
+                {"$ref": "#/components/schemas/Event_Deleted"},
+            ],
+            "discriminator": {
+                "propertyName": "type",
+                "mapping": {
+                    "Created": "#/components/schemas/Event_Created",
+                    "deleted": "#/components/schemas/Event_Deleted",
+                }
+            }
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(registry.schemas.remove("Event_Created").unwrap()).unwrap(),
+        json!({
+            "type": "object",
+            "title": "Created",
+            "required": ["type", "id", "name"],
+            "properties": {
+                "type": {"type": "string", "enum": ["Created"]},
+                "id": {"type": "integer", "format": "int32"},
+                "name": {"type": "string"},
+            }
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(registry.schemas.remove("Event_Deleted").unwrap()).unwrap(),
+        json!({
+            "type": "object",
+            "required": ["type", "id"],
+            "properties": {
+                "type": {"type": "string", "enum": ["deleted"]},
+                "id": {"type": "integer", "format": "int32"},
+                "why": {"type": "string"},
+            }
+        })
+    );
+
+    assert_eq!(
+        Event::parse_from_json(json!({"type": "Created", "id": 1, "name": "a"})).unwrap(),
+        Event::Created {
+            id: 1,
+            name: "a".to_string()
+        }
+    );
+    assert_eq!(
+        Event::parse_from_json(json!({"type": "deleted", "id": 2})).unwrap(),
+        Event::Deleted {
+            id: 2,
+            reason: None
+        }
+    );
+    let err = Event::parse_from_json(json!({"type": "Created", "id": "1"})).unwrap_err();
+    assert_eq!(
+        err.violations()
+            .iter()
+            .map(|violation| violation.pointer.as_str())
+            .collect::<Vec<_>>(),
+        vec!["/id", "/name"]
+    );
+
+    assert_eq!(
+        Event::Deleted {
+            id: 2,
+            reason: Some("spam".to_string())
+        }
+        .to_json(),
+        json!({"type": "deleted", "id": 2, "why": "spam"})
+    );
+}
+
+#[test]
+fn struct_variants_without_discriminator() {
+    #[derive(Union, Debug, PartialEq)]
+    enum Shape {
+        Circle { radius: f64 },
+        Rect { width: f64, height: f64 },
+    }
+
+    let mut registry = Registry::new();
+    Shape::register(&mut registry);
+    assert_eq!(
+        serde_json::to_value(registry.schemas.remove("Shape").unwrap()).unwrap(),
+        json!({
+            "oneOf": [
+                {
+                    "type": "object",
+                    "required": ["radius"],
+                    "properties": {"radius": {"type": "number", "format": "float64"}},
+                },
+                {
+                    "type": "object",
+                    "required": ["width", "height"],
+                    "properties": {
+                        "width": {"type": "number", "format": "float64"},
+                        "height": {"type": "number", "format": "float64"},
+                    },
+                },
+            ]
+        })
+    );
+
+    assert_eq!(
+        Shape::parse_from_json(json!({"width": 1.0, "height": 2.0})).unwrap(),
+        Shape::Rect {
+            width: 1.0,
+            height: 2.0
+        }
+    );
+    assert!(Shape::parse_from_json(json!({"width": 1.0})).is_err());
+    assert_eq!(
+        Shape::Circle { radius: 1.0 }.to_json(),
+        json!({"radius": 1.0})
+    );
+}
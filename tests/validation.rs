@@ -4,7 +4,7 @@ use poem::{
 };
 use poem_openapi::{
     registry::{MetaApi, MetaSchema},
-    types::ParseFromJSON,
+    types::{ParseFromJSON, ParseFromParameter},
     validation,
     validation::ValidatorMeta,
     Object, OpenApi, OpenApiService,
@@ -273,8 +273,26 @@ async fn param_validator() {
         .await;
     assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     assert_eq!(
-        resp.take_body().into_string().await.unwrap(),
-        "failed to parse param `v`: verification failed. maximum(100, exclusive: true)"
+        resp.headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&resp.take_body().into_string().await.unwrap())
+            .unwrap(),
+        json!({
+            "type": "about:blank",
+            "title": "Bad Request",
+            "status": 400,
+            "detail": "failed to parse param `v`: verification failed. maximum(100, exclusive: true)",
+            "parameter": "v",
+            "violations": [{
+                "pointer": "",
+                "validator": "maximum",
+                "expected": { "maximum": 100.0, "exclusiveMaximum": true },
+                "actual": "999",
+                "message": "verification failed. maximum(100, exclusive: true)",
+            }],
+        })
     );
 
     let meta: MetaApi = Api::meta().remove(0);
@@ -299,6 +317,133 @@ async fn param_validator() {
     assert_eq!(resp.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn param_validator_collect_violations() {
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "get")]
+        async fn test(
+            &self,
+            #[oai(name = "v", in = "query", maximum(value = "100"))] _v: i32,
+            #[oai(name = "n", in = "query")] _n: i32,
+            #[oai(name = "s", in = "query", max_length = "2")] _s: String,
+        ) {
+        }
+    }
+
+    let api = OpenApiService::new(Api).into_endpoint();
+    let mut resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/?v=999&s=abc"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body =
+        serde_json::from_str::<serde_json::Value>(&resp.take_body().into_string().await.unwrap())
+            .unwrap();
+    assert_eq!(body["parameters"], json!(["v", "n", "s"]));
+    assert_eq!(
+        body["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|violation| (
+                violation["parameter"].as_str().unwrap(),
+                violation["validator"].as_str()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("v", Some("maximum")),
+            ("n", None),
+            ("s", Some("maxLength"))
+        ]
+    );
+
+    let resp = api
+        .call(
+            Request::builder()
+                .uri(Uri::from_static("/?v=50&n=1&s=ab"))
+                .finish(),
+        )
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[test]
+fn collect_violations() {
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct B {
+        #[oai(max_length = "3")]
+        name: String,
+    }
+
+    #[derive(Object, Debug, Eq, PartialEq)]
+    struct A {
+        #[oai(maximum(value = "10"), multiple_of = "3")]
+        n: i32,
+        m: i32,
+        items: Vec<B>,
+    }
+
+    let err = A::parse_from_json(json!({
+        "n": 20,
+        "m": "abc",
+        "items": [{ "name": "abc" }, { "name": "abcd" }],
+    }))
+    .unwrap_err();
+    assert_eq!(
+        err.violations()
+            .iter()
+            .map(|violation| (violation.pointer.as_str(), violation.validator))
+            .collect::<Vec<_>>(),
+        vec![
+            ("/n", Some("multipleOf")),
+            ("/n", Some("maximum")),
+            ("/m", None),
+            ("/items/1/name", Some("maxLength")),
+        ]
+    );
+    assert_eq!(err.violations()[0].actual, Some(json!(20)));
+    assert_eq!(err.violations()[3].actual, Some(json!("abcd")));
+}
+
+#[test]
+fn collect_item_violations() {
+    use std::collections::HashMap;
+
+    let err = Vec::<i32>::parse_from_json(json!([1, "a", 2, "b"])).unwrap_err();
+    assert_eq!(
+        err.violations()
+            .iter()
+            .map(|violation| violation.pointer.as_str())
+            .collect::<Vec<_>>(),
+        vec!["/1", "/3"]
+    );
+
+    let err = Vec::<i32>::parse_from_parameters(&["1", "a", "2", "b"]).unwrap_err();
+    assert_eq!(
+        err.violations()
+            .iter()
+            .map(|violation| violation.pointer.as_str())
+            .collect::<Vec<_>>(),
+        vec!["/1", "/3"]
+    );
+
+    let err =
+        HashMap::<String, i32>::parse_from_json(json!({"a": "x", "b": 1, "c": "y"})).unwrap_err();
+    let mut pointers = err
+        .violations()
+        .iter()
+        .map(|violation| violation.pointer.as_str())
+        .collect::<Vec<_>>();
+    pointers.sort_unstable();
+    assert_eq!(pointers, vec!["/a", "/c"]);
+}
+
 #[test]
 fn test_option() {
     #[derive(Object, Debug, Eq, PartialEq)]